@@ -29,6 +29,7 @@ echo "  ✅ Binary installed to /usr/bin/ro-start"
 # Install desktop files
 install -Dm644 data/ro-start.desktop /usr/share/applications/ro-start.desktop
 install -Dm644 data/ro-start-autostart.desktop /etc/xdg/autostart/ro-start-autostart.desktop
+install -Dm644 data/ro-start-update-check.service /usr/lib/systemd/user/ro-start-update-check.service
 echo "  ✅ Desktop files installed"
 
 # Install icon
@@ -6,9 +6,30 @@ _ro_start_completions()
     COMPREPLY=()
     cur="${COMP_WORDS[COMP_CWORD]}"
     prev="${COMP_WORDS[COMP_CWORD-1]}"
-    opts="--help --version --no-startup --locale --debug"
+    local commands="info check-updates config report doctor --format"
+    opts="--help --version --no-startup --page --update-now --daemon --locale --debug"
 
     case "${prev}" in
+        --format)
+            if [[ "${COMP_WORDS[1]}" == "report" ]]; then
+                COMPREPLY=( $(compgen -W "markdown json tar-gz" -- ${cur}) )
+            else
+                COMPREPLY=( $(compgen -W "text json toml" -- ${cur}) )
+            fi
+            return 0
+            ;;
+        -o|--output)
+            COMPREPLY=( $(compgen -f -- ${cur}) )
+            return 0
+            ;;
+        config)
+            COMPREPLY=( $(compgen -W "get set" -- ${cur}) )
+            return 0
+            ;;
+        --page)
+            COMPREPLY=( $(compgen -W "home dashboard health services boot update drivers hardware software" -- ${cur}) )
+            return 0
+            ;;
         --locale)
             local locales="en_US tr_TR de es fr it ja ru zh"
             COMPREPLY=( $(compgen -W "${locales}" -- ${cur}) )
@@ -18,7 +39,12 @@ _ro_start_completions()
             ;;
     esac
 
-    COMPREPLY=( $(compgen -W "${opts}" -- ${cur}) )
+    if [[ "${COMP_WORDS[1]}" == "report" ]]; then
+        COMPREPLY=( $(compgen -W "--format --output --redact" -- ${cur}) )
+        return 0
+    fi
+
+    COMPREPLY=( $(compgen -W "${opts} ${commands}" -- ${cur}) )
     return 0
 }
 
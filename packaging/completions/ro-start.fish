@@ -2,6 +2,20 @@
 
 complete -c ro-start -l help -d 'Show help information'
 complete -c ro-start -l version -d 'Show version information'
-complete -c ro-start -l no-startup -d 'Do not show at startup'
+complete -c ro-start -l no-startup -d 'Exit quietly when launching at login is disabled'
+complete -c ro-start -l page -d 'Open a page' -xa 'home dashboard health services boot update drivers hardware software'
+complete -c ro-start -l update-now -d 'Open the update page and install updates'
+complete -c ro-start -l daemon -d 'Check for updates in the background'
 complete -c ro-start -l locale -d 'Set interface language' -xa 'en_US tr_TR de es fr it ja ru zh'
 complete -c ro-start -s d -l debug -d 'Enable debug logging'
+
+complete -c ro-start -n '__fish_use_subcommand' -a info -d 'Print system information'
+complete -c ro-start -n '__fish_use_subcommand' -a check-updates -d 'Check for pending updates'
+complete -c ro-start -n '__fish_use_subcommand' -a config -d 'Read or change settings'
+complete -c ro-start -n '__fish_use_subcommand' -a report -d 'Collect a system report for support requests'
+complete -c ro-start -n '__fish_use_subcommand' -a doctor -d 'Check the system for common problems'
+complete -c ro-start -n '__fish_seen_subcommand_from config' -a 'get set'
+complete -c ro-start -n '__fish_seen_subcommand_from info check-updates doctor' -l format -d 'Output format' -xa 'text json toml'
+complete -c ro-start -n '__fish_seen_subcommand_from report' -l format -d 'Report format' -xa 'markdown json tar-gz'
+complete -c ro-start -n '__fish_seen_subcommand_from report' -s o -l output -d 'Write the report to a file' -rF
+complete -c ro-start -n '__fish_seen_subcommand_from report' -l redact -d 'Replace the hostname, user name and IP addresses'
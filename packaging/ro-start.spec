@@ -45,6 +45,7 @@ install -Dm755 target/release/%{name} %{buildroot}%{_bindir}/%{name}
 # Install desktop files
 install -Dm644 data/%{name}.desktop %{buildroot}%{_datadir}/applications/%{name}.desktop
 install -Dm644 data/%{name}-autostart.desktop %{buildroot}%{_sysconfdir}/xdg/autostart/%{name}-autostart.desktop
+install -Dm644 data/%{name}-update-check.service %{buildroot}%{_userunitdir}/%{name}-update-check.service
 
 # Install icon
 install -Dm644 data/%{name}.png %{buildroot}%{_datadir}/icons/hicolor/512x512/apps/%{name}.png
@@ -80,6 +81,7 @@ cargo test --release
 %{_bindir}/%{name}
 %{_datadir}/applications/%{name}.desktop
 %config(noreplace) %{_sysconfdir}/xdg/autostart/%{name}-autostart.desktop
+%{_userunitdir}/%{name}-update-check.service
 %{_datadir}/icons/hicolor/512x512/apps/%{name}.png
 %{_datadir}/metainfo/org.osdev.ro_start.appdata.xml
 %{_mandir}/man1/%{name}.1*
@@ -0,0 +1,162 @@
+//! XDG autostart entry management
+//!
+//! Packages install `/etc/xdg/autostart/ro-start-autostart.desktop`. The user's
+//! choice is stored as an override with the same name in
+//! `~/.config/autostart`, which takes precedence over the system entry; a
+//! disabled override sets `Hidden=true`.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// File name shared by the system entry and the user override
+pub const ENTRY_NAME: &str = "ro-start-autostart.desktop";
+
+/// Whether Ro-Start is set to launch at login
+pub fn is_enabled() -> bool {
+    match user_dir() {
+        Some(dir) => is_enabled_in(&dir, &system_dirs()),
+        None => false,
+    }
+}
+
+/// Create or update the user override so that it matches `enabled`
+pub fn set_enabled(enabled: bool) -> Result<()> {
+    let dir = user_dir().context("Failed to get config directory")?;
+    set_enabled_in(&dir, enabled)
+}
+
+/// Update the autostart entry only when it differs from `enabled`
+pub fn sync(enabled: bool) -> Result<()> {
+    if is_enabled() == enabled {
+        return Ok(());
+    }
+    set_enabled(enabled)
+}
+
+fn user_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("autostart"))
+}
+
+/// `$XDG_CONFIG_DIRS/autostart`, defaulting to `/etc/xdg/autostart`
+fn system_dirs() -> Vec<PathBuf> {
+    let dirs = std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string());
+    dirs.split(':')
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| Path::new(dir).join("autostart"))
+        .collect()
+}
+
+/// The user entry wins over the system ones; the first system entry found is
+/// used otherwise, following the XDG autostart specification
+fn is_enabled_in(user_dir: &Path, system_dirs: &[PathBuf]) -> bool {
+    std::iter::once(user_dir)
+        .chain(system_dirs.iter().map(PathBuf::as_path))
+        .find_map(|dir| std::fs::read_to_string(dir.join(ENTRY_NAME)).ok())
+        .is_some_and(|entry| entry_enabled(&entry))
+}
+
+fn set_enabled_in(user_dir: &Path, enabled: bool) -> Result<()> {
+    std::fs::create_dir_all(user_dir).context("Failed to create autostart directory")?;
+
+    let path = user_dir.join(ENTRY_NAME);
+    std::fs::write(&path, entry_contents(enabled)).context("Failed to write autostart entry")?;
+
+    tracing::info!(
+        "Autostart {} in {:?}",
+        if enabled { "enabled" } else { "disabled" },
+        path
+    );
+    Ok(())
+}
+
+fn entry_contents(enabled: bool) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Ro-Start (Autostart)\n\
+         Comment=Run Ro-Start automatically at login\n\
+         Exec=ro-start --no-startup\n\
+         Icon=ro-start\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled={enabled}\n\
+         X-GNOME-Autostart-Delay=5\n\
+         Hidden={hidden}\n",
+        enabled = enabled,
+        hidden = !enabled,
+    )
+}
+
+/// Read `Hidden` and `X-GNOME-Autostart-enabled` from the `[Desktop Entry]` group
+fn entry_enabled(entry: &str) -> bool {
+    let mut in_group = false;
+    let mut enabled = true;
+
+    for line in entry.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_group {
+            continue;
+        }
+        match line.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+            Some(("Hidden", "true")) => enabled = false,
+            Some(("X-GNOME-Autostart-enabled", "false")) => enabled = false,
+            _ => {}
+        }
+    }
+
+    enabled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::FakeRoot;
+
+    #[test]
+    fn test_user_override_takes_precedence() {
+        let root = FakeRoot::new("autostart-override");
+        let user = root.join("home/autostart");
+        let system = root.join("etc/xdg/autostart");
+        std::fs::create_dir_all(&system).unwrap();
+        std::fs::write(
+            system.join(ENTRY_NAME),
+            include_str!("../data/ro-start-autostart.desktop"),
+        )
+        .unwrap();
+
+        let system_dirs = [system];
+        assert!(is_enabled_in(&user, &system_dirs));
+
+        set_enabled_in(&user, false).unwrap();
+        assert!(!is_enabled_in(&user, &system_dirs));
+        let entry = std::fs::read_to_string(user.join(ENTRY_NAME)).unwrap();
+        assert!(entry.contains("Hidden=true"));
+
+        set_enabled_in(&user, true).unwrap();
+        assert!(is_enabled_in(&user, &system_dirs));
+    }
+
+    #[test]
+    fn test_missing_entries_are_disabled() {
+        let root = FakeRoot::new("autostart-missing");
+        assert!(!is_enabled_in(&root.join("user"), &[root.join("system")]));
+    }
+
+    #[test]
+    fn test_entry_enabled_flags() {
+        assert!(entry_enabled("[Desktop Entry]\nHidden=false\n"));
+        assert!(!entry_enabled("[Desktop Entry]\nHidden=true\n"));
+        assert!(!entry_enabled(
+            "[Desktop Entry]\nX-GNOME-Autostart-enabled=false\n"
+        ));
+        // Keys in other groups are ignored
+        assert!(entry_enabled(
+            "[Desktop Entry]\nName=Ro-Start\n[Desktop Action new]\nHidden=true\n"
+        ));
+    }
+}
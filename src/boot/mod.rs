@@ -0,0 +1,411 @@
+//! Boot performance from `systemd-analyze`
+//!
+//! Parses `systemd-analyze time` (firmware, loader, kernel, initrd and
+//! userspace phases) and `systemd-analyze blame` (slowest units), and keeps a
+//! history of boot times in the config directory so a boot that got slower
+//! after an update stands out.
+
+use crate::error::{Result, RoStartError};
+use crate::package_manager::runner::{run_stdout, CommandRunner, SystemRunner};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Units shown from `systemd-analyze blame`
+const SLOWEST_UNITS: usize = 10;
+
+/// Boots kept in the history file
+const HISTORY_LIMIT: usize = 50;
+
+/// Previous boots the latest one is compared against
+const REGRESSION_WINDOW: usize = 10;
+
+/// Time spent in each boot phase, in milliseconds. Phases systemd cannot
+/// measure (e.g. firmware in a VM or initrd-less boots) are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootTimes {
+    pub firmware_ms: Option<u64>,
+    pub loader_ms: Option<u64>,
+    pub kernel_ms: Option<u64>,
+    pub initrd_ms: Option<u64>,
+    pub userspace_ms: Option<u64>,
+    pub total_ms: u64,
+}
+
+impl BootTimes {
+    /// Measured phases in boot order with their labels
+    pub fn phases(&self) -> Vec<(&'static str, u64)> {
+        [
+            ("Firmware", self.firmware_ms),
+            ("Boot Loader", self.loader_ms),
+            ("Kernel", self.kernel_ms),
+            ("Initrd", self.initrd_ms),
+            ("Userspace", self.userspace_ms),
+        ]
+        .into_iter()
+        .filter_map(|(label, ms)| Some((label, ms?)))
+        .collect()
+    }
+}
+
+/// Time a unit took to start
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnitTime {
+    pub unit: String,
+    pub time_ms: u64,
+}
+
+/// The current boot: phase times and the slowest units
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BootAnalysis {
+    pub times: BootTimes,
+    pub slowest_units: Vec<UnitTime>,
+}
+
+/// Analyze the current boot
+pub fn analyze() -> Result<BootAnalysis> {
+    analyze_with(&SystemRunner)
+}
+
+/// `analyze` using `runner` to run `systemd-analyze`
+pub fn analyze_with(runner: &impl CommandRunner) -> Result<BootAnalysis> {
+    let times = boot_times_with(runner)?;
+    let blame = run_stdout(runner, &["systemd-analyze", "blame", "--no-pager"])?;
+    let mut slowest_units = parse_blame(&blame);
+    slowest_units.truncate(SLOWEST_UNITS);
+    Ok(BootAnalysis {
+        times,
+        slowest_units,
+    })
+}
+
+/// Phase times of the current boot. Fails while the boot is still in progress.
+pub fn boot_times() -> Result<BootTimes> {
+    boot_times_with(&SystemRunner)
+}
+
+/// `boot_times` using `runner` to run `systemd-analyze`
+pub fn boot_times_with(runner: &impl CommandRunner) -> Result<BootTimes> {
+    let output = run_stdout(runner, &["systemd-analyze", "time", "--no-pager"])?;
+    parse_time(&output).ok_or_else(|| {
+        RoStartError::CommandFailed(format!(
+            "Unexpected systemd-analyze output: {}",
+            output.trim()
+        ))
+    })
+}
+
+/// Parse `systemd-analyze time`, e.g. "Startup finished in 2.5s (kernel) +
+/// 4.1s (userspace) = 6.6s"
+pub fn parse_time(output: &str) -> Option<BootTimes> {
+    let line = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Startup finished in "))?;
+    let (phases, total) = line.rsplit_once(" = ")?;
+
+    let mut times = BootTimes {
+        total_ms: parse_timespan(total)?,
+        ..BootTimes::default()
+    };
+    for phase in phases.split(" + ") {
+        let (span, label) = phase.rsplit_once(" (")?;
+        let ms = Some(parse_timespan(span)?);
+        match label.trim_end_matches(')') {
+            "firmware" => times.firmware_ms = ms,
+            "loader" => times.loader_ms = ms,
+            "kernel" => times.kernel_ms = ms,
+            "initrd" => times.initrd_ms = ms,
+            "userspace" => times.userspace_ms = ms,
+            other => tracing::debug!("Ignoring boot phase {:?}", other),
+        }
+    }
+    Some(times)
+}
+
+/// Parse `systemd-analyze blame`, slowest unit first
+pub fn parse_blame(output: &str) -> Vec<UnitTime> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (span, unit) = line.trim().rsplit_once(char::is_whitespace)?;
+            Some(UnitTime {
+                unit: unit.to_string(),
+                time_ms: parse_timespan(span)?,
+            })
+        })
+        .collect()
+}
+
+/// Milliseconds in a systemd time span such as "1min 2.003s" or "845ms"
+fn parse_timespan(span: &str) -> Option<u64> {
+    let mut total = 0.0;
+    for part in span.split_whitespace() {
+        let split = part.find(|c: char| c.is_alphabetic() || c == 'µ')?;
+        let (value, unit) = part.split_at(split);
+        let value: f64 = value.parse().ok()?;
+        let ms_per_unit = match unit {
+            "us" | "µs" => 0.001,
+            "ms" => 1.0,
+            "s" => 1_000.0,
+            "min" => 60_000.0,
+            "h" => 3_600_000.0,
+            "d" => 86_400_000.0,
+            _ => return None,
+        };
+        total += value * ms_per_unit;
+    }
+    Some(total.round() as u64)
+}
+
+/// "1 min 12.9 s", "12.3 s" or "845 ms"
+pub fn format_ms(ms: u64) -> String {
+    match ms {
+        0..=999 => format!("{} ms", ms),
+        1_000..=59_999 => format!("{:.1} s", ms as f64 / 1_000.0),
+        _ => format!(
+            "{} min {:.1} s",
+            ms / 60_000,
+            (ms % 60_000) as f64 / 1_000.0
+        ),
+    }
+}
+
+/// One boot in the history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootRecord {
+    /// `/proc/sys/kernel/random/boot_id`
+    pub boot_id: String,
+    /// Unix time the machine booted
+    pub booted_at: u64,
+    pub kernel: String,
+    pub times: BootTimes,
+}
+
+/// Past boot times, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootHistory {
+    pub boots: Vec<BootRecord>,
+}
+
+/// The latest boot is noticeably slower than the ones before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    /// Median total of the previous boots
+    pub usual_ms: u64,
+    pub latest_ms: u64,
+    /// Kernel of the previous boot when the latest boot runs a different one
+    pub previous_kernel: Option<String>,
+}
+
+impl BootHistory {
+    /// `$XDG_CONFIG_HOME/ro-start/boot-history.json`
+    pub fn path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| RoStartError::SystemInfo("No config directory".to_string()))?;
+        Ok(config_dir.join("ro-start").join("boot-history.json"))
+    }
+
+    /// Load the history, empty when the file does not exist yet
+    pub fn load_from(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| {
+                RoStartError::SystemInfo(format!("Invalid boot history {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            RoStartError::SystemInfo(format!("Failed to encode boot history: {}", e))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Add a boot unless it is already recorded, dropping the oldest boots past
+    /// the limit. Returns whether the history changed.
+    pub fn record(&mut self, record: BootRecord) -> bool {
+        if self.boots.iter().any(|boot| boot.boot_id == record.boot_id) {
+            return false;
+        }
+        self.boots.push(record);
+        let excess = self.boots.len().saturating_sub(HISTORY_LIMIT);
+        self.boots.drain(..excess);
+        true
+    }
+
+    /// Compare the latest boot with the median of the ones before it. A boot
+    /// counts as slower when it took a quarter and at least 2 seconds longer.
+    pub fn regression(&self) -> Option<Regression> {
+        let (latest, previous) = self.boots.split_last()?;
+        let window = &previous[previous.len().saturating_sub(REGRESSION_WINDOW)..];
+        if window.len() < 3 {
+            return None;
+        }
+
+        let mut totals: Vec<u64> = window.iter().map(|boot| boot.times.total_ms).collect();
+        totals.sort_unstable();
+        let usual_ms = totals[totals.len() / 2];
+        let latest_ms = latest.times.total_ms;
+        if latest_ms < usual_ms + 2_000 || latest_ms * 4 < usual_ms * 5 {
+            return None;
+        }
+
+        let previous_kernel = window
+            .last()
+            .filter(|boot| boot.kernel != latest.kernel)
+            .map(|boot| boot.kernel.clone());
+        Some(Regression {
+            usual_ms,
+            latest_ms,
+            previous_kernel,
+        })
+    }
+}
+
+/// Add the current boot with `times` to the history file and return the
+/// updated history
+pub fn record_boot(times: &BootTimes) -> Result<BootHistory> {
+    let path = BootHistory::path()?;
+    let mut history = BootHistory::load_from(&path)?;
+    let boot_id = std::fs::read_to_string("/proc/sys/kernel/random/boot_id")?;
+
+    let record = BootRecord {
+        boot_id: boot_id.trim().to_string(),
+        booted_at: sysinfo::System::boot_time(),
+        kernel: sysinfo::System::kernel_version().unwrap_or_default(),
+        times: times.clone(),
+    };
+    if history.record(record) {
+        tracing::info!("Recorded boot time of {}", format_ms(times.total_ms));
+        history.save_to(&path)?;
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::runner::FakeRunner;
+    use crate::test_util::FakeRoot;
+
+    const TIME_FIXTURE: &str = include_str!("fixtures/systemd-analyze-time.txt");
+    const TIME_VM_FIXTURE: &str = include_str!("fixtures/systemd-analyze-time-vm.txt");
+    const BLAME_FIXTURE: &str = include_str!("fixtures/systemd-analyze-blame.txt");
+
+    fn boot(id: &str, kernel: &str, total_ms: u64) -> BootRecord {
+        BootRecord {
+            boot_id: id.to_string(),
+            booted_at: 1_760_771_521,
+            kernel: kernel.to_string(),
+            times: BootTimes {
+                total_ms,
+                ..BootTimes::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let times = parse_time(TIME_FIXTURE).unwrap();
+        assert_eq!(times.firmware_ms, Some(7_542));
+        assert_eq!(times.loader_ms, Some(3_201));
+        assert_eq!(times.kernel_ms, Some(2_512));
+        assert_eq!(times.initrd_ms, Some(4_173));
+        assert_eq!(times.userspace_ms, Some(72_934));
+        assert_eq!(times.total_ms, 90_362);
+
+        let vm = parse_time(TIME_VM_FIXTURE).unwrap();
+        assert_eq!(vm.firmware_ms, None);
+        assert_eq!(vm.phases(), vec![("Kernel", 845), ("Userspace", 5_678)]);
+        assert_eq!(vm.total_ms, 6_523);
+
+        assert_eq!(
+            parse_time("Bootup is not yet finished (org.freedesktop.systemd1.Manager.FinishTimestampMonotonic=0)."),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_blame() {
+        let units = parse_blame(BLAME_FIXTURE);
+        assert_eq!(units.len(), 8);
+        assert_eq!(
+            units[0],
+            UnitTime {
+                unit: "NetworkManager-wait-online.service".to_string(),
+                time_ms: 62_003,
+            }
+        );
+        assert_eq!(units[5].time_ms, 845);
+        assert_eq!(units[7].time_ms, 0);
+        assert_eq!(format_ms(62_003), "1 min 2.0 s");
+        assert_eq!(format_ms(12_345), "12.3 s");
+        assert_eq!(format_ms(845), "845 ms");
+    }
+
+    #[test]
+    fn test_analyze_with_runner() {
+        let runner = FakeRunner::new()
+            .on(
+                &["systemd-analyze", "time", "--no-pager"],
+                0,
+                TIME_VM_FIXTURE,
+                "",
+            )
+            .on(
+                &["systemd-analyze", "blame", "--no-pager"],
+                0,
+                BLAME_FIXTURE,
+                "",
+            );
+        let analysis = analyze_with(&runner).unwrap();
+        assert_eq!(analysis.times.total_ms, 6_523);
+        assert_eq!(analysis.slowest_units.len(), 8);
+
+        let unfinished = FakeRunner::new().on(
+            &["systemd-analyze", "time", "--no-pager"],
+            1,
+            "",
+            "Bootup is not yet finished.",
+        );
+        assert!(analyze_with(&unfinished).is_err());
+    }
+
+    #[test]
+    fn test_history_record_and_regression() {
+        let root = FakeRoot::new("boot-history");
+        let path = root.join("boot-history.json");
+        let mut history = BootHistory::load_from(&path).unwrap();
+        assert!(history.boots.is_empty());
+
+        for (i, total) in [20_000, 21_000, 19_500, 20_500].into_iter().enumerate() {
+            assert!(history.record(boot(&format!("boot-{}", i), "6.17.1", total)));
+        }
+        assert!(!history.record(boot("boot-3", "6.17.1", 20_500)));
+        assert_eq!(history.regression(), None);
+
+        history.record(boot("boot-4", "6.17.2", 31_000));
+        assert_eq!(
+            history.regression(),
+            Some(Regression {
+                usual_ms: 20_500,
+                latest_ms: 31_000,
+                previous_kernel: Some("6.17.1".to_string()),
+            })
+        );
+
+        history.save_to(&path).unwrap();
+        assert_eq!(BootHistory::load_from(&path).unwrap(), history);
+
+        for i in 0..HISTORY_LIMIT {
+            history.record(boot(&format!("old-{}", i), "6.17.2", 20_000));
+        }
+        assert_eq!(history.boots.len(), HISTORY_LIMIT);
+        assert_eq!(history.boots[0].boot_id, "old-0");
+    }
+}
@@ -0,0 +1,317 @@
+//! Headless subcommands (`ro-start info`, `check-updates`, `config`, `report`,
+//! `doctor`)
+//!
+//! These never touch GTK, so they work over SSH and from provisioning scripts.
+//!
+//! Exit codes:
+//! - `0`: success; for `check-updates`, the system is up to date
+//! - `1`: the command failed (no package manager, check failed, config or
+//!   report I/O)
+//! - `2`: invalid usage (unknown subcommand, setting or value)
+//! - `100`: `check-updates` found pending updates
+//! - `101`: `check-updates` found pending security updates
+//! - `102`: `doctor` found warnings
+//! - `103`: `doctor` found critical problems
+
+use crate::config::AppConfig;
+use crate::doctor::Severity;
+use crate::output::OutputFormat;
+use crate::package_manager::{PackageManager, UpdateCheckOptions};
+use crate::report::{Redaction, Report, ReportFormat};
+use crate::system::SystemState;
+use clap::Subcommand;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+pub const EXIT_ERROR: u8 = 1;
+pub const EXIT_USAGE: u8 = 2;
+pub const EXIT_UPDATES_AVAILABLE: u8 = 100;
+pub const EXIT_SECURITY_UPDATES: u8 = 101;
+pub const EXIT_HEALTH_WARNING: u8 = 102;
+pub const EXIT_HEALTH_CRITICAL: u8 = 103;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print system information
+    Info {
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+
+    /// Check every update source and list pending updates
+    ///
+    /// Exits with 0 when up to date, 100 when updates are available and 101
+    /// when some of them are security updates.
+    CheckUpdates {
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+
+    /// Read or change settings in ~/.config/ro-start/config.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Collect a system report to attach to a support request
+    ///
+    /// Includes system and distribution details, the session, package managers
+    /// and pending updates, GPUs and drivers, disk usage and recent journal errors.
+    Report {
+        #[arg(long, value_enum, default_value_t)]
+        format: ReportFormat,
+        /// Write the report to FILE instead of standard output (tar-gz
+        /// defaults to ./ro-start-report.tar.gz)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Replace the hostname, user name and IP addresses
+        #[arg(long)]
+        redact: bool,
+    },
+
+    /// Check the system for common problems and suggest fixes
+    ///
+    /// Exits with 0 when everything is fine, 102 on warnings and 103 on
+    /// critical problems.
+    Doctor {
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print a setting, or the whole configuration without a key
+    Get {
+        /// Setting name, e.g. `language` or `quiet_hours.start`
+        key: Option<String>,
+    },
+
+    /// Change a setting
+    Set {
+        /// Setting name, e.g. `autostart`
+        key: String,
+        /// New value as TOML (`true`, `360`, `"tr_TR"`); bare words are strings
+        value: String,
+    },
+}
+
+/// Run a subcommand and return the process exit code
+pub fn run(command: Command) -> ExitCode {
+    match command {
+        Command::Info { format } => info(format),
+        Command::CheckUpdates { format } => check_updates(format),
+        Command::Config { action } => config(action),
+        Command::Report {
+            format,
+            output,
+            redact,
+        } => report(format, output, redact),
+        Command::Doctor { format } => doctor(format),
+    }
+}
+
+fn info(format: OutputFormat) -> ExitCode {
+    let info = SystemState::new().get_system_info();
+
+    if format != OutputFormat::Text {
+        return print_document(crate::output::system_document(&info, format));
+    }
+
+    println!("Operating System: {} {}", info.os_name, info.os_version);
+    println!("Desktop: {}", info.desktop_environment);
+    println!("Session: {}", info.session_summary());
+    println!("Kernel: {}", info.kernel_version);
+    println!("Hostname: {}", info.hostname);
+    println!("CPU: {}", info.cpu_info);
+    println!("Memory: {}", info.memory_info);
+    for disk in &info.disks {
+        println!("Storage {}: {}", disk.mount_point, disk.usage_summary());
+    }
+    for disk in info.low_space_disks() {
+        eprintln!(
+            "Warning: {} has only {} free",
+            disk.mount_point,
+            crate::system::format_size(disk.free_bytes)
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn check_updates(format: OutputFormat) -> ExitCode {
+    let backends = PackageManager::detect_all();
+    if backends.is_empty() {
+        eprintln!(
+            "Error: {}",
+            crate::error::RoStartError::PackageManagerNotFound
+        );
+        return ExitCode::from(EXIT_ERROR);
+    }
+
+    let config = AppConfig::load().unwrap_or_default();
+    let options = UpdateCheckOptions {
+        timeout: Duration::from_secs(config.update_check_timeout_secs),
+        ..Default::default()
+    };
+    let result = crate::runtime::block_on(PackageManager::check_all_updates_async(
+        &backends,
+        options,
+        |progress| tracing::debug!("Update check progress: {:?}", progress),
+    ));
+
+    let info = match result {
+        Ok(info) => info,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    if format != OutputFormat::Text {
+        let printed = print_document(crate::output::updates_document(&info, format));
+        if printed != ExitCode::SUCCESS {
+            return printed;
+        }
+    } else {
+        for source in info.sources.iter().filter(|s| !s.updates.is_empty()) {
+            println!("==> {} ({})", source.package_manager, source.updates.len());
+            for update in &source.updates {
+                if update.is_security() {
+                    println!("{} [security]", update);
+                } else {
+                    println!("{}", update);
+                }
+            }
+        }
+        println!("{}", info.message());
+    }
+
+    match (info.available, info.security_count) {
+        (false, _) => ExitCode::SUCCESS,
+        (true, 0) => ExitCode::from(EXIT_UPDATES_AVAILABLE),
+        (true, _) => ExitCode::from(EXIT_SECURITY_UPDATES),
+    }
+}
+
+fn config(action: ConfigAction) -> ExitCode {
+    let mut config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    match action {
+        ConfigAction::Get { key: None } => match toml::to_string_pretty(&config) {
+            Ok(contents) => print!("{}", contents),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(EXIT_ERROR);
+            }
+        },
+        ConfigAction::Get { key: Some(key) } => match config.get_value(&key) {
+            // Print strings without quotes so scripts can use them directly
+            Ok(Some(toml::Value::String(value))) => println!("{}", value),
+            Ok(Some(value)) => println!("{}", value),
+            Ok(None) => {
+                eprintln!("Error: '{}' is not set", key);
+                return ExitCode::from(EXIT_USAGE);
+            }
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                return ExitCode::from(EXIT_ERROR);
+            }
+        },
+        ConfigAction::Set { key, value } => {
+            if let Err(e) = config.set_value(&key, &value) {
+                eprintln!("Error: {:#}", e);
+                return ExitCode::from(EXIT_USAGE);
+            }
+            if let Err(e) = config.save() {
+                eprintln!("Error: {:#}", e);
+                return ExitCode::from(EXIT_ERROR);
+            }
+            // Keep the XDG autostart entry in line with the setting
+            if key == "autostart" {
+                if let Err(e) = crate::autostart::sync(config.autostart) {
+                    eprintln!("Error: {:#}", e);
+                    return ExitCode::from(EXIT_ERROR);
+                }
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn report(format: ReportFormat, output: Option<PathBuf>, redact: bool) -> ExitCode {
+    let config = AppConfig::load().unwrap_or_default();
+    let timeout = Duration::from_secs(config.update_check_timeout_secs);
+    let mut report = crate::runtime::block_on(Report::collect(timeout));
+    if redact {
+        report.redact(&Redaction::for_system(&report.system));
+    }
+
+    let output = match (format, output) {
+        (ReportFormat::TarGz, None) => Some(PathBuf::from("ro-start-report.tar.gz")),
+        (_, output) => output,
+    };
+    if let Err(e) = report.write(format, output.as_deref()) {
+        eprintln!("Error: {:#}", e);
+        return ExitCode::from(EXIT_ERROR);
+    }
+    if let Some(path) = output {
+        eprintln!("Report written to {}", path.display());
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn doctor(format: OutputFormat) -> ExitCode {
+    let results = crate::doctor::run_checks();
+
+    if format != OutputFormat::Text {
+        let printed = print_document(crate::output::health_document(&results, format));
+        if printed != ExitCode::SUCCESS {
+            return printed;
+        }
+    } else {
+        for result in &results {
+            println!(
+                "[{}] {}: {}",
+                result.severity,
+                result.check.title(),
+                result.summary
+            );
+            for detail in &result.details {
+                println!("    {}", detail);
+            }
+            if let Some(fix) = &result.fix {
+                let sudo = if fix.requires_root { "sudo " } else { "" };
+                println!("    Fix: {}{}", sudo, fix.command.join(" "));
+            }
+        }
+    }
+
+    match crate::doctor::overall_severity(&results) {
+        Severity::Critical => ExitCode::from(EXIT_HEALTH_CRITICAL),
+        Severity::Warning => ExitCode::from(EXIT_HEALTH_WARNING),
+        Severity::Ok | Severity::Info => ExitCode::SUCCESS,
+    }
+}
+
+fn print_document(document: anyhow::Result<String>) -> ExitCode {
+    match document {
+        Ok(document) => {
+            print!("{}", document);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
@@ -5,19 +5,98 @@ use std::path::PathBuf;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AppConfig {
+    /// Format of the file, to migrate settings written by older versions.
+    /// Files without it predate `CONFIG_VERSION` 2.
+    #[serde(default = "legacy_config_version")]
+    pub config_version: u32,
     pub app_name: String,
     pub version: String,
     pub autostart: bool,
     pub language: String,
+    /// Seconds before a running update check is aborted
+    pub update_check_timeout_secs: u64,
+    /// Minutes between checks in background mode (`--daemon`)
+    pub update_check_interval_mins: u64,
+    /// Window in which the background checker stays silent
+    pub quiet_hours: Option<QuietHours>,
+    /// Fingerprint of the update set the user was last notified about
+    pub last_notified_updates: Option<String>,
+    /// Unix time until which update notifications are snoozed
+    pub snoozed_until: Option<u64>,
+    /// Unix time at which the welcome window was first shown
+    pub first_run_at: Option<u64>,
+    /// Ro-Start version the welcome window was last shown for
+    pub last_seen_version: Option<String>,
+    /// Distribution release (`NAME VERSION`) the welcome window was last shown on
+    pub last_seen_distro: Option<String>,
+}
+
+/// Why the welcome window should open on its own at login
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WelcomeReason {
+    FirstRun,
+    AppUpdated { from: Option<String> },
+    DistroUpgraded { from: Option<String> },
+}
+
+/// Daily window given as local `HH:MM` times; may wrap past midnight
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+impl QuietHours {
+    /// Parse `HH:MM` into minutes since midnight
+    fn parse_time(time: &str) -> Option<u32> {
+        let (hours, minutes) = time.split_once(':')?;
+        let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+        (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+    }
+
+    /// Time left until the window ends, or `None` when `minute_of_day` is outside it
+    pub fn remaining(&self, minute_of_day: u32) -> Option<std::time::Duration> {
+        let start = Self::parse_time(&self.start)?;
+        let end = Self::parse_time(&self.end)?;
+
+        let inside = if start <= end {
+            (start..end).contains(&minute_of_day)
+        } else {
+            minute_of_day >= start || minute_of_day < end
+        };
+        if !inside {
+            return None;
+        }
+
+        let minutes = (end + 24 * 60 - minute_of_day) % (24 * 60);
+        Some(std::time::Duration::from_secs(u64::from(minutes) * 60))
+    }
+}
+
+/// Current `AppConfig::config_version`
+const CONFIG_VERSION: u32 = 2;
+
+fn legacy_config_version() -> u32 {
+    1
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            config_version: CONFIG_VERSION,
             app_name: "Ro-Start".to_string(),
-            version: "2.0.0".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
             autostart: false,
             language: "auto".to_string(),
+            update_check_timeout_secs: crate::package_manager::DEFAULT_UPDATE_CHECK_TIMEOUT
+                .as_secs(),
+            update_check_interval_mins: 6 * 60,
+            quiet_hours: None,
+            last_notified_updates: None,
+            snoozed_until: None,
+            first_run_at: None,
+            last_seen_version: None,
+            last_seen_distro: None,
         }
     }
 }
@@ -36,11 +115,137 @@ impl AppConfig {
         if path.exists() {
             let contents = std::fs::read_to_string(&path).context("Failed to read config file")?;
             let config: Self = toml::from_str(&contents).context("Failed to parse config file")?;
-            Ok(config)
+            Ok(config
+                .migrated(crate::autostart::is_enabled)
+                .with_usable_intervals())
         } else {
             tracing::info!("Config file not found, using defaults");
-            Ok(Self::default())
+            // Until the user decides, follow the autostart entry the package installed
+            Ok(Self {
+                autostart: crate::autostart::is_enabled(),
+                ..Self::default()
+            })
+        }
+    }
+
+    /// Silence update notifications for `duration`. The current update set is
+    /// forgotten so it is announced again once the snooze ends.
+    pub fn snooze(&mut self, duration: std::time::Duration) {
+        self.snoozed_until = Some(unix_now() + duration.as_secs());
+        self.last_notified_updates = None;
+    }
+
+    /// Whether update notifications are snoozed at `now` (Unix time)
+    pub fn is_snoozed(&self, now: u64) -> bool {
+        self.snoozed_until.is_some_and(|until| now < until)
+    }
+
+    /// Whether the welcome window has something new to show for `app_version`
+    /// running on `distro`; `None` when nothing changed since it was last seen
+    pub fn welcome_reason(&self, app_version: &str, distro: &str) -> Option<WelcomeReason> {
+        if self.first_run_at.is_none() {
+            return Some(WelcomeReason::FirstRun);
+        }
+        if self.last_seen_version.as_deref() != Some(app_version) {
+            return Some(WelcomeReason::AppUpdated {
+                from: self.last_seen_version.clone(),
+            });
         }
+        if self.last_seen_distro.as_deref() != Some(distro) {
+            return Some(WelcomeReason::DistroUpgraded {
+                from: self.last_seen_distro.clone(),
+            });
+        }
+        None
+    }
+
+    /// Record that the welcome window was shown for `app_version` on `distro`
+    pub fn mark_welcome_seen(&mut self, app_version: &str, distro: &str, now: u64) {
+        self.first_run_at.get_or_insert(now);
+        self.version = app_version.to_string();
+        self.last_seen_version = Some(app_version.to_string());
+        self.last_seen_distro = Some(distro.to_string());
+    }
+
+    /// Look up a setting by key; nested values use dots (`quiet_hours.start`).
+    /// Returns `None` for unknown keys and unset optional values.
+    pub fn get_value(&self, key: &str) -> Result<Option<toml::Value>> {
+        let mut value = toml::Value::try_from(self).context("Failed to serialize config")?;
+        for part in key.split('.') {
+            match value.get(part) {
+                Some(inner) => value = inner.clone(),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(value))
+    }
+
+    /// Change a setting from its command line form. `raw` is parsed as a TOML
+    /// value (`true`, `360`, `{ start = "22:00", end = "07:00" }`) and falls
+    /// back to a plain string. The result is validated but not saved.
+    pub fn set_value(&mut self, key: &str, raw: &str) -> Result<()> {
+        let new_value = toml::from_str::<toml::Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(raw.to_string()));
+
+        let mut root = toml::Value::try_from(&*self).context("Failed to serialize config")?;
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let last = parts.pop().context("Empty key")?;
+        let mut table = root.as_table_mut().context("Config is not a table")?;
+        for part in parts {
+            table = table
+                .entry(part)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .with_context(|| format!("'{}' is not a table", part))?;
+        }
+        table.insert(last.to_string(), new_value.clone());
+
+        let updated: Self = root
+            .try_into()
+            .with_context(|| format!("Invalid value for '{}': {}", key, raw))?;
+
+        // Unknown keys are dropped by deserialization
+        if updated.get_value(key)?.as_ref() != Some(&new_value) {
+            anyhow::bail!("Unknown setting '{}'", key);
+        }
+        updated.validate()?;
+
+        *self = updated;
+        Ok(())
+    }
+
+    /// Upgrade a file written by an older version. Version 1 always wrote
+    /// `autostart = false` without it meaning anything, so the autostart
+    /// entry decides instead, as for a new config.
+    fn migrated(mut self, autostart_enabled: impl FnOnce() -> bool) -> Self {
+        if self.config_version < 2 {
+            self.autostart = autostart_enabled();
+        }
+        self.config_version = CONFIG_VERSION;
+        self
+    }
+
+    /// Shortest allowed `update_check_interval_mins`
+    const MIN_CHECK_INTERVAL_MINS: u64 = 15;
+
+    /// Replace a zero timeout and a too short interval from a hand-edited file,
+    /// which would make every check fail or the background checker spin
+    fn with_usable_intervals(mut self) -> Self {
+        if self.update_check_timeout_secs == 0 {
+            tracing::warn!("Ignoring update_check_timeout_secs = 0, using the default");
+            self.update_check_timeout_secs = Self::default().update_check_timeout_secs;
+        }
+        if self.update_check_interval_mins < Self::MIN_CHECK_INTERVAL_MINS {
+            tracing::warn!(
+                "update_check_interval_mins = {} is too short, using {}",
+                self.update_check_interval_mins,
+                Self::MIN_CHECK_INTERVAL_MINS
+            );
+            self.update_check_interval_mins = Self::MIN_CHECK_INTERVAL_MINS;
+        }
+        self
     }
 
     /// Valid locale values for input validation
@@ -57,6 +262,22 @@ impl AppConfig {
                 Self::VALID_LOCALES
             );
         }
+        if self.update_check_timeout_secs == 0 {
+            anyhow::bail!("update_check_timeout_secs must be greater than zero");
+        }
+        if self.update_check_interval_mins < Self::MIN_CHECK_INTERVAL_MINS {
+            anyhow::bail!(
+                "update_check_interval_mins must be at least {}",
+                Self::MIN_CHECK_INTERVAL_MINS
+            );
+        }
+        if let Some(quiet) = &self.quiet_hours {
+            for time in [&quiet.start, &quiet.end] {
+                if QuietHours::parse_time(time).is_none() {
+                    anyhow::bail!("Invalid quiet hours time '{}', expected HH:MM", time);
+                }
+            }
+        }
         Ok(())
     }
 
@@ -89,6 +310,14 @@ impl AppConfig {
     }
 }
 
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +329,7 @@ mod tests {
         assert_eq!(config.version, "2.0.0");
         assert!(!config.autostart);
         assert_eq!(config.language, "auto");
+        assert_eq!(config.update_check_timeout_secs, 120);
     }
 
     #[test]
@@ -137,6 +367,163 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_zero_update_check_timeout_fails_validation() {
+        let config = AppConfig {
+            update_check_timeout_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_check_interval_validation() {
+        let config = AppConfig {
+            update_check_interval_mins: 5,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_old_autostart_default_is_migrated() {
+        let old: AppConfig = toml::from_str("autostart = false\nlanguage = \"de\"\n").unwrap();
+        assert_eq!(old.config_version, 1);
+        let migrated = old.migrated(|| true);
+        assert!(migrated.autostart);
+        assert_eq!(migrated.language, "de");
+        assert_eq!(migrated.config_version, CONFIG_VERSION);
+
+        // A choice saved by this version is kept
+        let saved = toml::to_string(&AppConfig::default()).unwrap();
+        let current: AppConfig = toml::from_str(&saved).unwrap();
+        assert!(!current.migrated(|| true).autostart);
+    }
+
+    #[test]
+    fn test_loaded_intervals_are_usable() {
+        let config: AppConfig =
+            toml::from_str("update_check_timeout_secs = 0\nupdate_check_interval_mins = 0\n")
+                .unwrap();
+        let config = config.with_usable_intervals();
+        assert_eq!(config.update_check_timeout_secs, 120);
+        assert_eq!(config.update_check_interval_mins, 15);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_quiet_hours_wrapping_midnight() {
+        let quiet = QuietHours {
+            start: "22:00".to_string(),
+            end: "07:30".to_string(),
+        };
+        let minutes = |h: u32, m: u32| h * 60 + m;
+
+        assert_eq!(
+            quiet.remaining(minutes(23, 0)),
+            Some(std::time::Duration::from_secs(510 * 60))
+        );
+        assert_eq!(
+            quiet.remaining(minutes(6, 30)),
+            Some(std::time::Duration::from_secs(60 * 60))
+        );
+        assert_eq!(quiet.remaining(minutes(7, 30)), None);
+        assert_eq!(quiet.remaining(minutes(12, 0)), None);
+
+        let invalid = AppConfig {
+            quiet_hours: Some(QuietHours {
+                start: "25:00".to_string(),
+                end: "07:00".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_snooze_forgets_notified_updates() {
+        let mut config = AppConfig {
+            last_notified_updates: Some("0123456789abcdef".to_string()),
+            ..Default::default()
+        };
+        let now = unix_now();
+        assert!(!config.is_snoozed(now));
+
+        config.snooze(std::time::Duration::from_secs(24 * 60 * 60));
+        assert!(config.is_snoozed(now));
+        assert!(!config.is_snoozed(now + 24 * 60 * 60 + 1));
+        assert_eq!(config.last_notified_updates, None);
+    }
+
+    #[test]
+    fn test_welcome_shown_once_per_release() {
+        let mut config = AppConfig::default();
+        assert_eq!(
+            config.welcome_reason("2.0.0", "Ubuntu 24.04"),
+            Some(WelcomeReason::FirstRun)
+        );
+
+        config.mark_welcome_seen("2.0.0", "Ubuntu 24.04", 1_700_000_000);
+        assert_eq!(config.welcome_reason("2.0.0", "Ubuntu 24.04"), None);
+
+        assert_eq!(
+            config.welcome_reason("2.1.0", "Ubuntu 24.04"),
+            Some(WelcomeReason::AppUpdated {
+                from: Some("2.0.0".to_string())
+            })
+        );
+        assert_eq!(
+            config.welcome_reason("2.0.0", "Ubuntu 26.04"),
+            Some(WelcomeReason::DistroUpgraded {
+                from: Some("Ubuntu 24.04".to_string())
+            })
+        );
+
+        // The first run time is kept across releases
+        config.mark_welcome_seen("2.1.0", "Ubuntu 26.04", 1_800_000_000);
+        assert_eq!(config.first_run_at, Some(1_700_000_000));
+        assert_eq!(config.welcome_reason("2.1.0", "Ubuntu 26.04"), None);
+    }
+
+    #[test]
+    fn test_get_and_set_values() {
+        let mut config = AppConfig::default();
+        assert_eq!(
+            config.get_value("language").unwrap(),
+            Some(toml::Value::String("auto".to_string()))
+        );
+        assert_eq!(config.get_value("quiet_hours.start").unwrap(), None);
+
+        config.set_value("autostart", "true").unwrap();
+        config.set_value("language", "tr_TR").unwrap();
+        config
+            .set_value("update_check_interval_mins", "60")
+            .unwrap();
+        config
+            .set_value("quiet_hours", r#"{ start = "22:00", end = "07:00" }"#)
+            .unwrap();
+        config.set_value("quiet_hours.end", "06:30").unwrap();
+
+        assert!(config.autostart);
+        assert_eq!(config.language, "tr_TR");
+        assert_eq!(config.update_check_interval_mins, 60);
+        assert_eq!(
+            config.get_value("quiet_hours.end").unwrap(),
+            Some(toml::Value::String("06:30".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_set_value_rejects_bad_input() {
+        let mut config = AppConfig::default();
+        assert!(config.set_value("no_such_key", "1").is_err());
+        assert!(config.set_value("autostart", "sometimes").is_err());
+        assert!(config.set_value("language", "xx_XX").is_err());
+        assert!(config.set_value("update_check_interval_mins", "1").is_err());
+        assert_eq!(config.language, "auto");
+    }
+
     #[test]
     fn test_config_serialization_roundtrip() {
         let config = AppConfig::default();
@@ -146,5 +533,16 @@ mod tests {
         assert_eq!(config.version, deserialized.version);
         assert_eq!(config.autostart, deserialized.autostart);
         assert_eq!(config.language, deserialized.language);
+
+        let config = AppConfig {
+            quiet_hours: Some(QuietHours {
+                start: "22:00".to_string(),
+                end: "07:00".to_string(),
+            }),
+            ..Default::default()
+        };
+        let deserialized: AppConfig =
+            toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(config.quiet_hours, deserialized.quiet_hours);
     }
 }
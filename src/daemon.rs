@@ -0,0 +1,144 @@
+//! Background update checker (`ro-start --daemon`)
+//!
+//! Runs without a window: checks every update source on the interval from
+//! `AppConfig`, notifies once per distinct update set and stays silent during
+//! the configured quiet hours.
+
+use crate::config::AppConfig;
+use crate::error::RoStartError;
+use crate::notifications::UpdateAction;
+use crate::package_manager::{PackageManager, UpdateCheckOptions};
+use std::time::Duration;
+
+/// Run the checker until the process is stopped
+pub fn run() {
+    crate::runtime::block_on(async {
+        let backends = PackageManager::detect_all();
+        if backends.is_empty() {
+            tracing::error!(
+                "Background checker stopped: {}",
+                RoStartError::PackageManagerNotFound
+            );
+            return;
+        }
+        tracing::info!("Background update checker started for {:?}", backends);
+
+        loop {
+            // Re-read the config every round so changes from the GUI apply without a restart
+            let config = AppConfig::load().unwrap_or_default();
+
+            if let Some(wait) = quiet_time_left(&config) {
+                tracing::debug!("Quiet hours, next check in {:?}", wait);
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+
+            // Record every boot, also when the GUI is not opened
+            if let Err(e) =
+                crate::boot::boot_times().and_then(|times| crate::boot::record_boot(&times))
+            {
+                tracing::debug!("Boot time not recorded: {}", e);
+            }
+
+            let interval = config.update_check_interval_mins;
+            check_and_notify(
+                &backends,
+                Duration::from_secs(config.update_check_timeout_secs),
+            )
+            .await;
+            tokio::time::sleep(Duration::from_secs(interval * 60)).await;
+        }
+    });
+}
+
+/// Check once and notify when the pending update set changed since the last notification
+async fn check_and_notify(backends: &[PackageManager], timeout: Duration) {
+    let options = UpdateCheckOptions {
+        timeout,
+        ..Default::default()
+    };
+
+    let info = match PackageManager::check_all_updates_async(backends, options, |_| {}).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::warn!("Background update check failed: {}", e);
+            return;
+        }
+    };
+    tracing::info!("Background check: {}", info.message());
+
+    // The check can take minutes; pick up changes made meanwhile by the GUI,
+    // `ro-start config set` or a snooze so saving below does not undo them
+    let mut config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!("Failed to reload config: {}", e);
+            return;
+        }
+    };
+
+    if config.is_snoozed(crate::config::unix_now()) {
+        tracing::debug!(
+            "Update notifications snoozed until {:?}",
+            config.snoozed_until
+        );
+        return;
+    }
+
+    let fingerprint = info.available.then(|| info.fingerprint());
+    if fingerprint == config.last_notified_updates {
+        return;
+    }
+
+    if info.available {
+        crate::notifications::notify_updates_available(
+            info.count,
+            info.security_count,
+            handle_action,
+        );
+    }
+
+    config.last_notified_updates = fingerprint;
+    if let Err(e) = config.save() {
+        tracing::warn!("Failed to record notified updates: {}", e);
+    }
+}
+
+/// React to a button on the update notification
+fn handle_action(action: UpdateAction) {
+    match action {
+        UpdateAction::UpdateNow => launch_gui(&["--page", "update", "--update-now"]),
+        UpdateAction::ViewDetails => launch_gui(&["--page", "update"]),
+        UpdateAction::RemindTomorrow => {
+            let mut config = AppConfig::load().unwrap_or_default();
+            config.snooze(Duration::from_secs(24 * 60 * 60));
+            if let Err(e) = config.save() {
+                tracing::warn!("Failed to snooze update notifications: {}", e);
+            }
+        }
+    }
+}
+
+/// Start Ro-Start, or hand `args` to the instance that is already running
+fn launch_gui(args: &[&str]) {
+    let exe = std::env::current_exe().unwrap_or_else(|_| "ro-start".into());
+    match std::process::Command::new(&exe).args(args).spawn() {
+        // Reap the child so it does not linger as a zombie
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(e) => tracing::error!("Failed to launch {:?}: {}", exe, e),
+    }
+}
+
+/// Time left in the quiet hours window at the current local time
+fn quiet_time_left(config: &AppConfig) -> Option<Duration> {
+    let quiet = config.quiet_hours.as_ref()?;
+    let now = glib::DateTime::now_local().ok()?;
+    let minute_of_day = (now.hour() * 60 + now.minute()) as u32;
+    // Resume on the minute after the window closes
+    quiet
+        .remaining(minute_of_day)
+        .map(|left| left.saturating_sub(Duration::from_secs(now.seconds() as u64)))
+        .map(|left| left.max(Duration::from_secs(60)))
+}
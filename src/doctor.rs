@@ -0,0 +1,696 @@
+//! System health checks (`ro-start doctor` and the Health page)
+//!
+//! Every check in `Check::ALL` looks at one thing that commonly goes wrong and
+//! returns a severity, a summary and, where there is a safe one, a fix command.
+//! Checks take a `CheckContext` so they can run against a fake root and a
+//! `FakeRunner` in the tests.
+
+use crate::error::{Result, RoStartError};
+use crate::package_manager::runner::{run_stdout, CommandRunner, SystemRunner};
+use crate::package_manager::PackageManager;
+use crate::services::UnitScope;
+use crate::system::{SystemInfo, SystemState};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+/// Below this much RAM a missing swap space is a warning rather than a remark
+const SWAP_RECOMMENDED_BELOW_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+/// How bad a finding is, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Ok,
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn icon_name(self) -> &'static str {
+        match self {
+            Self::Ok => "emblem-ok-symbolic",
+            Self::Info => "dialog-information-symbolic",
+            Self::Warning => "dialog-warning-symbolic",
+            Self::Critical => "dialog-error-symbolic",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "ok"),
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+/// A command that resolves a finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    /// Button label, e.g. "Clean package cache"
+    pub label: String,
+    pub command: Vec<String>,
+    /// Run through pkexec
+    pub requires_root: bool,
+    /// Question to confirm before running, for fixes that close applications
+    pub confirmation: Option<String>,
+}
+
+impl Fix {
+    fn new(label: &str, command: &[&str], requires_root: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            requires_root,
+            confirmation: None,
+        }
+    }
+
+    fn with_confirmation(mut self, confirmation: &str) -> Self {
+        self.confirmation = Some(confirmation.to_string());
+        self
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckResult {
+    pub check: Check,
+    pub severity: Severity,
+    pub summary: String,
+    /// Affected items: mount points, units, files, ...
+    pub details: Vec<String>,
+    pub fix: Option<Fix>,
+}
+
+impl CheckResult {
+    fn new(check: Check, severity: Severity, summary: impl Into<String>) -> Self {
+        Self {
+            check,
+            severity,
+            summary: summary.into(),
+            details: Vec::new(),
+            fix: None,
+        }
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// What the checks look at
+pub struct CheckContext<'a, R: CommandRunner> {
+    pub runner: &'a R,
+    /// Filesystem root for `/proc`, `/run` and friends
+    pub root: &'a Path,
+    pub system: &'a SystemInfo,
+    /// Native package manager, if one was detected
+    pub package_manager: Option<&'a PackageManager>,
+}
+
+/// The registry of health checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Check {
+    DiskSpace,
+    FailedUnits,
+    PackageState,
+    MissingFirmware,
+    ClockSync,
+    Swap,
+    PendingReboot,
+}
+
+impl Check {
+    /// Run order
+    pub const ALL: [Self; 7] = [
+        Self::DiskSpace,
+        Self::FailedUnits,
+        Self::PackageState,
+        Self::MissingFirmware,
+        Self::ClockSync,
+        Self::Swap,
+        Self::PendingReboot,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::DiskSpace => "Disk space",
+            Self::FailedUnits => "System services",
+            Self::PackageState => "Package database",
+            Self::MissingFirmware => "Firmware",
+            Self::ClockSync => "Clock synchronization",
+            Self::Swap => "Swap space",
+            Self::PendingReboot => "Pending restart",
+        }
+    }
+
+    /// Run the check; `None` when it does not apply to this system, e.g. the
+    /// package database check on a package manager without an audit command
+    pub fn run(self, ctx: &CheckContext<'_, impl CommandRunner>) -> Option<CheckResult> {
+        match self {
+            Self::DiskSpace => Some(check_disk_space(ctx)),
+            Self::FailedUnits => check_failed_units(ctx),
+            Self::PackageState => check_package_state(ctx),
+            Self::MissingFirmware => check_missing_firmware(ctx),
+            Self::ClockSync => check_clock_sync(ctx),
+            Self::Swap => Some(check_swap(ctx)),
+            Self::PendingReboot => ctx.package_manager.map(|pm| check_pending_reboot(ctx, pm)),
+        }
+    }
+}
+
+/// Run every check on this system
+pub fn run_checks() -> Vec<CheckResult> {
+    let system = SystemState::new().get_system_info();
+    let package_manager = PackageManager::detect().ok();
+    run_checks_with(&CheckContext {
+        runner: &SystemRunner,
+        root: Path::new("/"),
+        system: &system,
+        package_manager: package_manager.as_ref(),
+    })
+}
+
+/// `run_checks` against an arbitrary context
+pub fn run_checks_with(ctx: &CheckContext<'_, impl CommandRunner>) -> Vec<CheckResult> {
+    Check::ALL
+        .into_iter()
+        .filter_map(|check| check.run(ctx))
+        .collect()
+}
+
+/// Worst severity among `results`
+pub fn overall_severity(results: &[CheckResult]) -> Severity {
+    results
+        .iter()
+        .map(|result| result.severity)
+        .max()
+        .unwrap_or(Severity::Ok)
+}
+
+/// Run a fix, through pkexec when it needs root, streaming its output.
+/// Returns the exit code of the command.
+pub fn apply_fix(fix: &Fix, on_line: impl FnMut(String)) -> Result<i32> {
+    apply_fix_with(&SystemRunner, fix, on_line)
+}
+
+/// `apply_fix` using `runner` to execute the command
+pub fn apply_fix_with(
+    runner: &impl CommandRunner,
+    fix: &Fix,
+    mut on_line: impl FnMut(String),
+) -> Result<i32> {
+    let mut cmd = fix.command.clone();
+    if fix.requires_root {
+        cmd.insert(0, "pkexec".to_string());
+    }
+    tracing::info!("Applying fix: {}", cmd.join(" "));
+
+    let code = runner.run_streaming(&cmd, &mut on_line).map_err(|e| {
+        tracing::error!("Failed to start fix {:?}: {}", cmd, e);
+        RoStartError::CommandFailed(format!("Failed to start {}: {}", fix.command[0], e))
+    })?;
+
+    Ok(code.unwrap_or(-1))
+}
+
+fn check_disk_space(ctx: &CheckContext<'_, impl CommandRunner>) -> CheckResult {
+    let low = ctx.system.low_space_disks();
+    if low.is_empty() {
+        return CheckResult::new(Check::DiskSpace, Severity::Ok, "Enough free space");
+    }
+
+    let details = low
+        .iter()
+        .map(|disk| {
+            format!(
+                "{}: {} free",
+                disk.mount_point,
+                crate::system::format_size(disk.free_bytes)
+            )
+        })
+        .collect();
+    // Under 1% free, updates and logins start failing
+    let severity = if low
+        .iter()
+        .any(|disk| disk.free_bytes.saturating_mul(100) < disk.total_bytes)
+    {
+        Severity::Critical
+    } else {
+        Severity::Warning
+    };
+
+    let result = CheckResult::new(Check::DiskSpace, severity, "Running out of disk space")
+        .with_details(details);
+    let clean_command: Option<&[&str]> = match ctx.package_manager {
+        Some(PackageManager::Apt) => Some(&["apt-get", "clean"]),
+        Some(PackageManager::Dnf) => Some(&["dnf", "clean", "packages"]),
+        Some(PackageManager::Pacman) => Some(&["pacman", "-Sc", "--noconfirm"]),
+        Some(PackageManager::Zypper) => Some(&["zypper", "clean", "--all"]),
+        _ => None,
+    };
+    match clean_command {
+        Some(command) => result.with_fix(Fix::new("Clean package cache", command, true)),
+        None => result,
+    }
+}
+
+fn check_failed_units(ctx: &CheckContext<'_, impl CommandRunner>) -> Option<CheckResult> {
+    let system = crate::services::failed_units_with(ctx.runner, UnitScope::System).ok()?;
+    // There is no user manager over SSH or in a root shell
+    let user = crate::services::failed_units_with(ctx.runner, UnitScope::User).unwrap_or_default();
+
+    let units: Vec<String> = system
+        .iter()
+        .map(|unit| unit.unit.clone())
+        .chain(user.iter().map(|unit| format!("{} (user)", unit.unit)))
+        .collect();
+
+    Some(if units.is_empty() {
+        CheckResult::new(Check::FailedUnits, Severity::Ok, "No failed services")
+    } else {
+        CheckResult::new(
+            Check::FailedUnits,
+            Severity::Warning,
+            format!("{} service(s) failed", units.len()),
+        )
+        .with_details(units)
+    })
+}
+
+fn check_package_state(ctx: &CheckContext<'_, impl CommandRunner>) -> Option<CheckResult> {
+    match ctx.package_manager? {
+        PackageManager::Apt => {
+            // dpkg --audit prints the broken packages and exits 0 either way
+            let output = run_stdout(ctx.runner, &["dpkg", "--audit"]).ok()?;
+            let details: Vec<String> = output
+                .lines()
+                .filter(|line| line.starts_with(' '))
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect();
+
+            Some(if output.trim().is_empty() {
+                CheckResult::new(Check::PackageState, Severity::Ok, "No broken packages")
+            } else {
+                CheckResult::new(
+                    Check::PackageState,
+                    Severity::Critical,
+                    "Some packages are not fully installed",
+                )
+                .with_details(details)
+                .with_fix(Fix::new(
+                    "Finish configuring packages",
+                    &["dpkg", "--configure", "-a"],
+                    true,
+                ))
+            })
+        }
+        PackageManager::Pacman => {
+            // pacman -Dk exits non-zero and lists problems on stderr
+            let cmd: Vec<String> = ["pacman", "-Dk"].iter().map(|s| s.to_string()).collect();
+            let output = ctx.runner.run(&cmd).ok()?;
+
+            Some(if output.success() {
+                CheckResult::new(Check::PackageState, Severity::Ok, "No broken packages")
+            } else {
+                let details = String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .map(|line| line.trim_start_matches("error: ").trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect();
+                CheckResult::new(
+                    Check::PackageState,
+                    Severity::Critical,
+                    "The package database has missing dependencies or conflicts",
+                )
+                .with_details(details)
+            })
+        }
+        _ => None,
+    }
+}
+
+fn check_missing_firmware(ctx: &CheckContext<'_, impl CommandRunner>) -> Option<CheckResult> {
+    let output = run_stdout(
+        ctx.runner,
+        &["journalctl", "-k", "-b", "-q", "--no-pager", "-o", "cat"],
+    )
+    .ok()?;
+
+    let mut files: Vec<String> = output
+        .lines()
+        .filter_map(|line| {
+            // "iwlwifi 0000:00:14.3: Direct firmware load for iwlwifi-x.ucode failed with error -2"
+            let (_, rest) = line.split_once("Direct firmware load for ")?;
+            let (file, _) = rest.split_once(" failed")?;
+            Some(file.to_string())
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+
+    Some(if files.is_empty() {
+        CheckResult::new(Check::MissingFirmware, Severity::Ok, "No missing firmware")
+    } else {
+        CheckResult::new(
+            Check::MissingFirmware,
+            Severity::Warning,
+            "Drivers could not load their firmware; install your distribution's firmware package (usually linux-firmware)",
+        )
+        .with_details(files)
+    })
+}
+
+fn check_clock_sync(ctx: &CheckContext<'_, impl CommandRunner>) -> Option<CheckResult> {
+    let output = run_stdout(
+        ctx.runner,
+        &["timedatectl", "show", "-p", "NTP", "-p", "NTPSynchronized"],
+    )
+    .ok()?;
+    let property = |key: &str| {
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(|value| value.trim() == "yes")
+    };
+
+    Some(match (property("NTP"), property("NTPSynchronized")) {
+        (_, Some(true)) => {
+            CheckResult::new(Check::ClockSync, Severity::Ok, "The clock is synchronized")
+        }
+        (Some(false), _) => CheckResult::new(
+            Check::ClockSync,
+            Severity::Warning,
+            "Network time synchronization is turned off",
+        )
+        .with_fix(Fix::new(
+            "Turn on network time",
+            &["timedatectl", "set-ntp", "true"],
+            false,
+        )),
+        _ => CheckResult::new(
+            Check::ClockSync,
+            Severity::Info,
+            "The clock has not been synchronized yet",
+        ),
+    })
+}
+
+fn check_swap(ctx: &CheckContext<'_, impl CommandRunner>) -> CheckResult {
+    // /proc/swaps has a header line, then one line per swap device or zram
+    let devices: Vec<String> = std::fs::read_to_string(ctx.root.join("proc/swaps"))
+        .unwrap_or_default()
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect();
+
+    if !devices.is_empty() {
+        return CheckResult::new(Check::Swap, Severity::Ok, "Swap is enabled")
+            .with_details(devices);
+    }
+    let severity = if ctx.system.total_memory < SWAP_RECOMMENDED_BELOW_BYTES {
+        Severity::Warning
+    } else {
+        Severity::Info
+    };
+    CheckResult::new(
+        Check::Swap,
+        severity,
+        "No swap space; the system may freeze when memory runs out",
+    )
+}
+
+fn check_pending_reboot(
+    ctx: &CheckContext<'_, impl CommandRunner>,
+    package_manager: &PackageManager,
+) -> CheckResult {
+    let status =
+        package_manager.restart_status_with(ctx.runner, ctx.root, &ctx.system.kernel_version);
+
+    if status.reboot_required {
+        CheckResult::new(
+            Check::PendingReboot,
+            Severity::Warning,
+            "Restart to finish installing updates",
+        )
+        .with_details(status.reboot_packages)
+        .with_fix(
+            Fix::new("Restart now", &["systemctl", "reboot"], false).with_confirmation(
+                "Save your work before continuing. All open applications will be closed.",
+            ),
+        )
+    } else if !status.services.is_empty() {
+        // Restarting the services is enough, the computer can keep running
+        let mut command = vec!["systemctl", "restart"];
+        command.extend(status.services.iter().map(String::as_str));
+        let fix = Fix::new("Restart services", &command, true);
+        CheckResult::new(
+            Check::PendingReboot,
+            Severity::Info,
+            "Some services still run replaced libraries",
+        )
+        .with_fix(fix)
+        .with_details(status.services)
+    } else if status.services_unknown {
+        CheckResult::new(
+            Check::PendingReboot,
+            Severity::Info,
+            "No restart pending, but outdated services could not be checked",
+        )
+        .with_details(vec![
+            "Checking system services needs root or a tool such as needrestart".to_string(),
+        ])
+    } else {
+        CheckResult::new(Check::PendingReboot, Severity::Ok, "No restart needed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::runner::FakeRunner;
+    use crate::system::DiskInfo;
+    use crate::test_util::FakeRoot;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn system_with(disks: Vec<DiskInfo>, total_memory: u64) -> SystemInfo {
+        let mut system = SystemState::new().get_system_info();
+        system.disks = disks;
+        system.total_memory = total_memory;
+        system.kernel_version = "6.8.0-45-generic".to_string();
+        system
+    }
+
+    fn disk(mount_point: &str, total_bytes: u64, free_bytes: u64) -> DiskInfo {
+        DiskInfo {
+            device: "/dev/sda1".to_string(),
+            mount_point: mount_point.to_string(),
+            file_system: "ext4".to_string(),
+            total_bytes,
+            used_bytes: total_bytes - free_bytes,
+            free_bytes,
+        }
+    }
+
+    #[test]
+    fn test_healthy_apt_system() {
+        let root = FakeRoot::new("doctor-healthy");
+        std::fs::create_dir_all(root.join("proc")).unwrap();
+        std::fs::write(
+            root.join("proc/swaps"),
+            "Filename\tType\tSize\tUsed\tPriority\n/dev/zram0 partition 8388604 0 100\n",
+        )
+        .unwrap();
+
+        let runner = FakeRunner::new()
+            .on(
+                &["systemctl", "--failed", "--output=json", "--no-pager"],
+                0,
+                "[]",
+                "",
+            )
+            .on(&["dpkg", "--audit"], 0, "", "")
+            .on(
+                &["journalctl", "-k", "-b", "-q", "--no-pager", "-o", "cat"],
+                0,
+                "usb 1-2: new high-speed USB device\n",
+                "",
+            )
+            .on(
+                &["timedatectl", "show", "-p", "NTP", "-p", "NTPSynchronized"],
+                0,
+                "NTP=yes\nNTPSynchronized=yes\n",
+                "",
+            )
+            .on(&["needrestart", "-b"], 0, "NEEDRESTART-VER: 3.6\n", "");
+        let system = system_with(vec![disk("/", 100 * GIB, 40 * GIB)], 16 * GIB);
+
+        let results = run_checks_with(&CheckContext {
+            runner: &runner,
+            root: &root,
+            system: &system,
+            package_manager: Some(&PackageManager::Apt),
+        });
+
+        assert_eq!(
+            results.iter().map(|r| r.check).collect::<Vec<_>>(),
+            Check::ALL
+        );
+        for result in &results {
+            assert_eq!(result.severity, Severity::Ok, "{:?}", result);
+        }
+        assert_eq!(overall_severity(&results), Severity::Ok);
+    }
+
+    #[test]
+    fn test_problems_are_reported_with_fixes() {
+        let root = FakeRoot::new("doctor-problems");
+        std::fs::create_dir_all(root.join("proc")).unwrap();
+        std::fs::write(
+            root.join("proc/swaps"),
+            "Filename\tType\tSize\tUsed\tPriority\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("run")).unwrap();
+        std::fs::write(root.join("run/reboot-required"), "").unwrap();
+
+        let runner = FakeRunner::new()
+            .on(
+                &["systemctl", "--failed", "--output=json", "--no-pager"],
+                0,
+                include_str!("services/fixtures/systemctl-failed.json"),
+                "",
+            )
+            .on(
+                &["systemctl", "--user", "--failed", "--output=json", "--no-pager"],
+                0,
+                include_str!("services/fixtures/systemctl-user-failed.json"),
+                "",
+            )
+            .on(
+                &["dpkg", "--audit"],
+                0,
+                "The following packages are only half configured:\n libc-bin  GNU C Library\n",
+                "",
+            )
+            .on(
+                &["journalctl", "-k", "-b", "-q", "--no-pager", "-o", "cat"],
+                0,
+                "iwlwifi 0000:00:14.3: Direct firmware load for iwlwifi-so-a0-gf-a0-89.ucode failed with error -2\n\
+                 iwlwifi 0000:00:14.3: Direct firmware load for iwlwifi-so-a0-gf-a0-89.ucode failed with error -2\n",
+                "",
+            )
+            .on(
+                &["timedatectl", "show", "-p", "NTP", "-p", "NTPSynchronized"],
+                0,
+                "NTP=no\nNTPSynchronized=no\n",
+                "",
+            );
+        let system = system_with(vec![disk("/", 100 * GIB, GIB / 2)], 4 * GIB);
+        let ctx = CheckContext {
+            runner: &runner,
+            root: &root,
+            system: &system,
+            package_manager: Some(&PackageManager::Apt),
+        };
+        let results = run_checks_with(&ctx);
+        let result = |check: Check| results.iter().find(|r| r.check == check).unwrap();
+
+        assert_eq!(result(Check::DiskSpace).severity, Severity::Critical);
+        assert_eq!(result(Check::DiskSpace).details, ["/: 0.5 GB free"]);
+        assert_eq!(
+            result(Check::DiskSpace).fix.as_ref().unwrap().command,
+            ["apt-get", "clean"]
+        );
+        assert_eq!(
+            result(Check::FailedUnits).details,
+            [
+                "cups.service",
+                "systemd-networkd-wait-online.service",
+                "mnt-backup.mount",
+                "pipewire-pulse.service (user)",
+            ]
+        );
+        assert_eq!(result(Check::PackageState).severity, Severity::Critical);
+        assert_eq!(
+            result(Check::PackageState).details,
+            ["libc-bin  GNU C Library"]
+        );
+        assert_eq!(
+            result(Check::MissingFirmware).details,
+            ["iwlwifi-so-a0-gf-a0-89.ucode"]
+        );
+        assert_eq!(result(Check::ClockSync).severity, Severity::Warning);
+        assert_eq!(result(Check::Swap).severity, Severity::Warning);
+        assert_eq!(result(Check::PendingReboot).severity, Severity::Warning);
+        // Restarting closes every application, so it must be confirmed first
+        assert!(result(Check::PendingReboot)
+            .fix
+            .as_ref()
+            .unwrap()
+            .confirmation
+            .is_some());
+        assert_eq!(
+            result(Check::ClockSync).fix.as_ref().unwrap().confirmation,
+            None
+        );
+        assert_eq!(overall_severity(&results), Severity::Critical);
+    }
+
+    #[test]
+    fn test_checks_that_do_not_apply_are_skipped() {
+        let system = system_with(Vec::new(), 16 * GIB);
+        let results = run_checks_with(&CheckContext {
+            runner: &FakeRunner::new(),
+            root: Path::new("/nonexistent"),
+            system: &system,
+            package_manager: Some(&PackageManager::Apk),
+        });
+
+        let checks: Vec<Check> = results.iter().map(|r| r.check).collect();
+        assert_eq!(
+            checks,
+            [Check::DiskSpace, Check::Swap, Check::PendingReboot]
+        );
+    }
+
+    #[test]
+    fn test_apply_fix_uses_pkexec_for_root() {
+        let runner = FakeRunner::new()
+            .on(
+                &["pkexec", "dpkg", "--configure", "-a"],
+                0,
+                "Setting up libc-bin\n",
+                "",
+            )
+            .on(&["timedatectl", "set-ntp", "true"], 0, "", "");
+
+        let mut lines = Vec::new();
+        let fix = Fix::new("Configure", &["dpkg", "--configure", "-a"], true);
+        assert_eq!(
+            apply_fix_with(&runner, &fix, |line| lines.push(line)).unwrap(),
+            0
+        );
+        assert_eq!(lines, ["Setting up libc-bin"]);
+
+        let fix = Fix::new("NTP", &["timedatectl", "set-ntp", "true"], false);
+        assert_eq!(apply_fix_with(&runner, &fix, |_| {}).unwrap(), 0);
+    }
+}
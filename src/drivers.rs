@@ -0,0 +1,370 @@
+//! GPU detection and proprietary driver suggestions
+//!
+//! GPUs are the display controllers among the PCI devices read by
+//! `crate::hardware`; every lookup takes a filesystem root so it can be tested
+//! against a fake sysfs tree.
+
+use crate::error::{Result, RoStartError};
+use crate::package_manager::runner::{CommandRunner, SystemRunner};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+/// PCI vendor ids of the GPU vendors we know about
+const VENDOR_NVIDIA: u16 = 0x10de;
+const VENDOR_AMD: u16 = 0x1002;
+const VENDOR_INTEL: u16 = 0x8086;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+    Other(u16),
+}
+
+impl GpuVendor {
+    fn from_id(id: u16) -> Self {
+        match id {
+            VENDOR_NVIDIA => Self::Nvidia,
+            VENDOR_AMD => Self::Amd,
+            VENDOR_INTEL => Self::Intel,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl fmt::Display for GpuVendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nvidia => write!(f, "NVIDIA"),
+            Self::Amd => write!(f, "AMD"),
+            Self::Intel => write!(f, "Intel"),
+            Self::Other(id) => write!(f, "{:04x}", id),
+        }
+    }
+}
+
+/// A display controller on the PCI bus
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Gpu {
+    /// PCI slot, e.g. `0000:01:00.0`
+    pub address: String,
+    pub vendor: GpuVendor,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    /// Model name from the PCI id database, when installed
+    pub model: Option<String>,
+    /// Kernel driver bound to the device (`nvidia`, `nouveau`, `amdgpu`, `i915`, ...)
+    pub driver: Option<String>,
+    /// Whether the firmware used this GPU for the boot display
+    pub boot_vga: bool,
+}
+
+impl Gpu {
+    /// Model name, falling back to the vendor and PCI ids
+    pub fn name(&self) -> String {
+        match &self.model {
+            Some(model) => format!("{} {}", self.vendor, model),
+            None => format!(
+                "{} [{:04x}:{:04x}]",
+                self.vendor, self.vendor_id, self.device_id
+            ),
+        }
+    }
+
+    /// An NVIDIA GPU that is not running the proprietary driver
+    pub fn wants_proprietary_driver(&self) -> bool {
+        self.vendor == GpuVendor::Nvidia && self.driver.as_deref() != Some("nvidia")
+    }
+}
+
+/// Distribution tool that installs the recommended proprietary driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverTool {
+    /// Ubuntu and derivatives
+    UbuntuDrivers,
+    /// Manjaro Hardware Detection
+    Mhwd,
+    /// Fedora with the RPM Fusion nonfree repository
+    Akmods,
+}
+
+impl fmt::Display for DriverTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UbuntuDrivers => write!(f, "ubuntu-drivers"),
+            Self::Mhwd => write!(f, "mhwd"),
+            Self::Akmods => write!(f, "RPM Fusion akmods"),
+        }
+    }
+}
+
+/// How to install the proprietary driver on this system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriverSuggestion {
+    pub tool: DriverTool,
+    /// Command to run as root
+    pub command: Vec<String>,
+    /// Step the user has to take before the command can succeed, if any
+    pub note: Option<String>,
+}
+
+/// Enumerate the GPUs of this machine
+pub fn detect_gpus() -> Vec<Gpu> {
+    detect_gpus_in(Path::new("/"))
+}
+
+/// `detect_gpus` against an arbitrary filesystem `root`
+pub fn detect_gpus_in(root: &Path) -> Vec<Gpu> {
+    let devices_dir = root.join("sys/bus/pci/devices");
+
+    let mut gpus: Vec<Gpu> = crate::hardware::read_pci_devices(root)
+        .into_iter()
+        // PCI base class 0x03 is "Display controller"
+        .filter(|device| device.class >> 8 == 0x03)
+        .map(|device| Gpu {
+            boot_vga: std::fs::read_to_string(devices_dir.join(&device.address).join("boot_vga"))
+                .is_ok_and(|value| value.trim() == "1"),
+            address: device.address,
+            vendor: GpuVendor::from_id(device.vendor_id),
+            vendor_id: device.vendor_id,
+            device_id: device.device_id,
+            model: device.model,
+            driver: device.driver,
+        })
+        .collect();
+
+    // Boot display first, then by slot
+    gpus.sort_by(|a, b| b.boot_vga.cmp(&a.boot_vga).then(a.address.cmp(&b.address)));
+    gpus
+}
+
+/// Suggest the distribution's way to install the proprietary driver, if one of
+/// `gpus` needs it
+pub fn recommend_driver(gpus: &[Gpu]) -> Option<DriverSuggestion> {
+    recommend_driver_in(Path::new("/"), gpus)
+}
+
+/// `recommend_driver` against an arbitrary filesystem `root`
+pub fn recommend_driver_in(root: &Path, gpus: &[Gpu]) -> Option<DriverSuggestion> {
+    if !gpus.iter().any(Gpu::wants_proprietary_driver) {
+        return None;
+    }
+    let has_binary = |name: &str| root.join("usr/bin").join(name).exists();
+    let command = |args: &[&str]| args.iter().map(|s| s.to_string()).collect();
+
+    if has_binary("ubuntu-drivers") {
+        Some(DriverSuggestion {
+            tool: DriverTool::UbuntuDrivers,
+            command: command(&["ubuntu-drivers", "install"]),
+            note: None,
+        })
+    } else if has_binary("mhwd") {
+        Some(DriverSuggestion {
+            tool: DriverTool::Mhwd,
+            command: command(&["mhwd", "--auto", "pci", "nonfree", "0300"]),
+            note: None,
+        })
+    } else if has_binary("dnf") {
+        let rpmfusion_enabled = std::fs::read_dir(root.join("etc/yum.repos.d"))
+            .map(|entries| {
+                entries.flatten().any(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .starts_with("rpmfusion-nonfree")
+                })
+            })
+            .unwrap_or(false);
+        Some(DriverSuggestion {
+            tool: DriverTool::Akmods,
+            command: command(&["dnf", "install", "-y", "akmod-nvidia"]),
+            note: (!rpmfusion_enabled)
+                .then(|| "Enable the RPM Fusion nonfree repository first".to_string()),
+        })
+    } else {
+        None
+    }
+}
+
+/// Run the suggested install command through pkexec, streaming its output.
+/// Returns the exit code of the command.
+pub fn install_driver(suggestion: &DriverSuggestion, on_line: impl FnMut(String)) -> Result<i32> {
+    install_driver_with(&SystemRunner, suggestion, on_line)
+}
+
+/// `install_driver` using `runner` to execute the command
+pub fn install_driver_with(
+    runner: &impl CommandRunner,
+    suggestion: &DriverSuggestion,
+    mut on_line: impl FnMut(String),
+) -> Result<i32> {
+    let mut cmd = suggestion.command.clone();
+    cmd.insert(0, "pkexec".to_string());
+    tracing::info!("Installing driver: {}", cmd.join(" "));
+
+    let code = runner.run_streaming(&cmd, &mut on_line).map_err(|e| {
+        tracing::error!("Failed to start driver install {:?}: {}", cmd, e);
+        RoStartError::CommandFailed(format!("Failed to start {}: {}", cmd[1], e))
+    })?;
+
+    Ok(code.unwrap_or(-1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::runner::FakeRunner;
+    use crate::test_util::FakeRoot;
+
+    /// Add a PCI device to the fake sysfs tree, bound to `driver` if given
+    fn add_pci_device(
+        root: &Path,
+        address: &str,
+        class: &str,
+        vendor: &str,
+        device: &str,
+        driver: Option<&str>,
+    ) {
+        let dir = root.join("sys/bus/pci/devices").join(address);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("class"), format!("{}\n", class)).unwrap();
+        std::fs::write(dir.join("vendor"), format!("{}\n", vendor)).unwrap();
+        std::fs::write(dir.join("device"), format!("{}\n", device)).unwrap();
+        if let Some(driver) = driver {
+            let target = root.join("sys/bus/pci/drivers").join(driver);
+            std::fs::create_dir_all(&target).unwrap();
+            std::os::unix::fs::symlink(&target, dir.join("driver")).unwrap();
+        }
+    }
+
+    const PCI_IDS: &str = "\
+# pci.ids excerpt
+10de  NVIDIA Corporation
+\t2684  AD102 [GeForce RTX 4090]
+\t\t1043 889d  ROG Strix RTX 4090
+\t2782  AD104 [GeForce RTX 4070 Ti]
+8086  Intel Corporation
+\t9a49  TigerLake-LP GT2 [Iris Xe Graphics]
+";
+
+    #[test]
+    fn test_detect_gpus_from_sysfs() {
+        let root = FakeRoot::new("drivers-hybrid");
+        add_pci_device(
+            &root,
+            "0000:00:02.0",
+            "0x030000",
+            "0x8086",
+            "0x9a49",
+            Some("i915"),
+        );
+        add_pci_device(
+            &root,
+            "0000:01:00.0",
+            "0x030200",
+            "0x10de",
+            "0x2684",
+            Some("nouveau"),
+        );
+        // Audio function of the same card is not a GPU
+        add_pci_device(&root, "0000:01:00.1", "0x040300", "0x10de", "0x22ba", None);
+        std::fs::write(
+            root.join("sys/bus/pci/devices/0000:00:02.0/boot_vga"),
+            "1\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("usr/share/hwdata")).unwrap();
+        std::fs::write(root.join("usr/share/hwdata/pci.ids"), PCI_IDS).unwrap();
+
+        let gpus = detect_gpus_in(&root);
+        assert_eq!(gpus.len(), 2);
+
+        assert_eq!(gpus[0].vendor, GpuVendor::Intel);
+        assert!(gpus[0].boot_vga);
+        assert_eq!(gpus[0].driver.as_deref(), Some("i915"));
+        assert_eq!(gpus[0].name(), "Intel TigerLake-LP GT2 [Iris Xe Graphics]");
+
+        assert_eq!(gpus[1].address, "0000:01:00.0");
+        assert_eq!(gpus[1].vendor, GpuVendor::Nvidia);
+        assert_eq!(gpus[1].driver.as_deref(), Some("nouveau"));
+        assert_eq!(gpus[1].model.as_deref(), Some("AD102 [GeForce RTX 4090]"));
+        assert!(gpus[1].wants_proprietary_driver());
+    }
+
+    #[test]
+    fn test_unknown_device_name_falls_back_to_ids() {
+        let root = FakeRoot::new("drivers-unknown");
+        add_pci_device(&root, "0000:03:00.0", "0x030000", "0x1002", "0x744c", None);
+
+        let gpus = detect_gpus_in(&root);
+        assert_eq!(gpus[0].vendor, GpuVendor::Amd);
+        assert_eq!(gpus[0].driver, None);
+        assert_eq!(gpus[0].name(), "AMD [1002:744c]");
+        assert!(!gpus[0].wants_proprietary_driver());
+    }
+
+    #[test]
+    fn test_recommend_driver_per_distribution() {
+        let nvidia = Gpu {
+            address: "0000:01:00.0".to_string(),
+            vendor: GpuVendor::Nvidia,
+            vendor_id: VENDOR_NVIDIA,
+            device_id: 0x2684,
+            model: None,
+            driver: Some("nouveau".to_string()),
+            boot_vga: true,
+        };
+
+        let ubuntu = FakeRoot::new("drivers-ubuntu");
+        std::fs::create_dir_all(ubuntu.join("usr/bin")).unwrap();
+        std::fs::write(ubuntu.join("usr/bin/ubuntu-drivers"), "").unwrap();
+        let suggestion = recommend_driver_in(&ubuntu, std::slice::from_ref(&nvidia)).unwrap();
+        assert_eq!(suggestion.tool, DriverTool::UbuntuDrivers);
+        assert_eq!(suggestion.command, ["ubuntu-drivers", "install"]);
+
+        let fedora = FakeRoot::new("drivers-fedora");
+        std::fs::create_dir_all(fedora.join("usr/bin")).unwrap();
+        std::fs::write(fedora.join("usr/bin/dnf"), "").unwrap();
+        let suggestion = recommend_driver_in(&fedora, std::slice::from_ref(&nvidia)).unwrap();
+        assert_eq!(suggestion.tool, DriverTool::Akmods);
+        assert!(suggestion.note.is_some());
+
+        std::fs::create_dir_all(fedora.join("etc/yum.repos.d")).unwrap();
+        std::fs::write(fedora.join("etc/yum.repos.d/rpmfusion-nonfree.repo"), "").unwrap();
+        let suggestion = recommend_driver_in(&fedora, std::slice::from_ref(&nvidia)).unwrap();
+        assert_eq!(suggestion.note, None);
+
+        // Nothing to suggest once the proprietary driver is in use
+        let installed = Gpu {
+            driver: Some("nvidia".to_string()),
+            ..nvidia
+        };
+        assert_eq!(recommend_driver_in(&ubuntu, &[installed]), None);
+    }
+
+    #[test]
+    fn test_install_driver_uses_pkexec() {
+        let runner = FakeRunner::new().on(
+            &["pkexec", "mhwd", "--auto", "pci", "nonfree", "0300"],
+            0,
+            "> Installing video-nvidia...\n",
+            "",
+        );
+        let suggestion = DriverSuggestion {
+            tool: DriverTool::Mhwd,
+            command: ["mhwd", "--auto", "pci", "nonfree", "0300"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            note: None,
+        };
+
+        let mut lines = Vec::new();
+        let code = install_driver_with(&runner, &suggestion, |line| lines.push(line)).unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(lines, ["> Installing video-nvidia..."]);
+    }
+}
@@ -20,6 +20,12 @@ pub enum RoStartError {
 
     #[error("Update check failed: {0}")]
     UpdateCheckFailed(String),
+
+    #[error("Update check timed out after {0} seconds")]
+    UpdateCheckTimedOut(u64),
+
+    #[error("Update check cancelled")]
+    UpdateCheckCancelled,
 }
 
 #[allow(dead_code)]
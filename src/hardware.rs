@@ -0,0 +1,653 @@
+//! Hardware inventory: firmware, batteries and peripherals
+//!
+//! Everything is read from sysfs (`/sys/class/dmi/id`, `/sys/class/power_supply`,
+//! `/sys/bus/usb/devices` and `/sys/bus/pci/devices`), with PCI names from the
+//! `pci.ids` database; every lookup takes a filesystem root so it can be tested
+//! against a fake sysfs tree.
+
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Values vendors leave in DMI fields they did not fill in
+const DMI_PLACEHOLDERS: &[&str] = &[
+    "To Be Filled By O.E.M.",
+    "To be filled by O.E.M.",
+    "Default string",
+    "System manufacturer",
+    "System Product Name",
+    "System Version",
+    "Not Applicable",
+    "Not Specified",
+    "None",
+    "0123456789",
+];
+
+/// USB vendor id of the Linux Foundation, used by the virtual root hubs
+const USB_VENDOR_LINUX_FOUNDATION: u16 = 0x1d6b;
+
+/// Locations of the PCI id database, relative to the root
+const PCI_IDS_PATHS: &[&str] = &[
+    "usr/share/hwdata/pci.ids",
+    "usr/share/misc/pci.ids",
+    "usr/share/pci.ids",
+];
+
+/// Firmware identity from `/sys/class/dmi/id`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Dmi {
+    pub system_vendor: Option<String>,
+    pub product_name: Option<String>,
+    pub product_version: Option<String>,
+    /// Form factor, e.g. "Laptop" or "Desktop"
+    pub chassis: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_name: Option<String>,
+    pub board_version: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    pub bios_date: Option<String>,
+}
+
+impl Dmi {
+    /// Vendor and model, e.g. "LENOVO ThinkPad T14 Gen 3"
+    pub fn model(&self) -> Option<String> {
+        join_some(&[
+            &self.system_vendor,
+            &self.product_name,
+            &self.product_version,
+        ])
+    }
+
+    pub fn board(&self) -> Option<String> {
+        join_some(&[&self.board_vendor, &self.board_name, &self.board_version])
+    }
+
+    /// Firmware version and release date
+    pub fn bios(&self) -> Option<String> {
+        let version = join_some(&[&self.bios_vendor, &self.bios_version])?;
+        Some(match &self.bios_date {
+            Some(date) => format!("{} ({})", version, date),
+            None => version,
+        })
+    }
+}
+
+/// A system battery from `/sys/class/power_supply`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Battery {
+    /// Kernel name, e.g. `BAT0`
+    pub name: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub technology: Option<String>,
+    /// "Charging", "Discharging", "Full", ...
+    pub status: Option<String>,
+    /// Current charge in percent
+    pub capacity_percent: Option<u8>,
+    /// Full charge capacity relative to the design capacity, in percent
+    pub health_percent: Option<u8>,
+    pub cycle_count: Option<u32>,
+}
+
+impl Battery {
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(capacity) = self.capacity_percent {
+            parts.push(format!("{}%", capacity));
+        }
+        if let Some(status) = &self.status {
+            parts.push(status.to_lowercase());
+        }
+        if let Some(health) = self.health_percent {
+            parts.push(format!("health {}%", health));
+        }
+        if let Some(cycles) = self.cycle_count {
+            parts.push(format!("{} cycles", cycles));
+        }
+        parts.join(", ")
+    }
+}
+
+/// A device connected over USB
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsbDevice {
+    /// sysfs name, e.g. `1-2`
+    pub port: String,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+}
+
+impl UsbDevice {
+    pub fn name(&self) -> String {
+        join_some(&[&self.manufacturer, &self.product])
+            .unwrap_or_else(|| format!("[{:04x}:{:04x}]", self.vendor_id, self.product_id))
+    }
+}
+
+/// A device on the PCI bus
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PciDevice {
+    /// PCI slot, e.g. `0000:00:02.0`
+    pub address: String,
+    /// Class code without the programming interface, e.g. `0x0300`
+    pub class: u16,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    /// Names from the PCI id database, when installed
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub driver: Option<String>,
+}
+
+impl PciDevice {
+    pub fn name(&self) -> String {
+        join_some(&[&self.vendor, &self.model])
+            .unwrap_or_else(|| format!("[{:04x}:{:04x}]", self.vendor_id, self.device_id))
+    }
+
+    /// Name of the PCI base class
+    pub fn class_name(&self) -> &'static str {
+        match self.class >> 8 {
+            0x01 => "Storage controller",
+            0x02 => "Network controller",
+            0x03 => "Display controller",
+            0x04 => "Multimedia controller",
+            0x05 => "Memory controller",
+            0x06 => "Bridge",
+            0x07 => "Communication controller",
+            0x08 => "System peripheral",
+            0x09 => "Input device controller",
+            0x0c => "Serial bus controller",
+            0x0d => "Wireless controller",
+            0x10 => "Encryption controller",
+            0x11 => "Signal processing controller",
+            0x12 => "Processing accelerator",
+            _ => "Other",
+        }
+    }
+}
+
+/// Everything the Hardware page shows
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HardwareInfo {
+    pub dmi: Dmi,
+    pub batteries: Vec<Battery>,
+    /// `None` when the machine has no AC adapter, e.g. desktops
+    pub on_ac_power: Option<bool>,
+    pub usb_devices: Vec<UsbDevice>,
+    pub pci_devices: Vec<PciDevice>,
+}
+
+impl HardwareInfo {
+    /// Inventory of this machine
+    pub fn detect() -> Self {
+        Self::detect_in(Path::new("/"))
+    }
+
+    /// `detect` against an arbitrary filesystem `root`
+    pub fn detect_in(root: &Path) -> Self {
+        let (batteries, on_ac_power) = read_power_supplies(root);
+        Self {
+            dmi: read_dmi(root),
+            batteries,
+            on_ac_power,
+            usb_devices: read_usb_devices(root),
+            pci_devices: read_pci_devices(root),
+        }
+    }
+
+    /// Plain text summary for support requests
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let unknown = || "Unknown".to_string();
+
+        let _ = writeln!(text, "Model: {}", self.dmi.model().unwrap_or_else(unknown));
+        if let Some(chassis) = &self.dmi.chassis {
+            let _ = writeln!(text, "Chassis: {}", chassis);
+        }
+        let _ = writeln!(
+            text,
+            "Motherboard: {}",
+            self.dmi.board().unwrap_or_else(unknown)
+        );
+        let _ = writeln!(text, "BIOS: {}", self.dmi.bios().unwrap_or_else(unknown));
+
+        for battery in &self.batteries {
+            let _ = writeln!(text, "Battery {}: {}", battery.name, battery.summary());
+        }
+        if let Some(online) = self.on_ac_power {
+            let _ = writeln!(
+                text,
+                "AC power: {}",
+                if online { "connected" } else { "disconnected" }
+            );
+        }
+
+        if !self.usb_devices.is_empty() {
+            let _ = writeln!(text, "\nUSB devices:");
+            for device in &self.usb_devices {
+                let _ = writeln!(
+                    text,
+                    "  {:04x}:{:04x} {}",
+                    device.vendor_id,
+                    device.product_id,
+                    device.name()
+                );
+            }
+        }
+
+        if !self.pci_devices.is_empty() {
+            let _ = writeln!(text, "\nPCI devices:");
+            for device in &self.pci_devices {
+                let _ = writeln!(
+                    text,
+                    "  {} {}: {} [{:04x}:{:04x}]{}",
+                    device.address,
+                    device.class_name(),
+                    device.name(),
+                    device.vendor_id,
+                    device.device_id,
+                    device
+                        .driver
+                        .as_ref()
+                        .map(|driver| format!(" ({})", driver))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        text
+    }
+}
+
+fn read_dmi(root: &Path) -> Dmi {
+    let dir = root.join("sys/class/dmi/id");
+    let read = |file: &str| {
+        read_value(&dir.join(file)).filter(|value| !DMI_PLACEHOLDERS.contains(&value.as_str()))
+    };
+
+    Dmi {
+        system_vendor: read("sys_vendor"),
+        product_name: read("product_name"),
+        product_version: read("product_version"),
+        chassis: read("chassis_type")
+            .and_then(|value| value.parse().ok())
+            .and_then(chassis_name)
+            .map(str::to_string),
+        board_vendor: read("board_vendor"),
+        board_name: read("board_name"),
+        board_version: read("board_version"),
+        bios_vendor: read("bios_vendor"),
+        bios_version: read("bios_version"),
+        bios_date: read("bios_date"),
+    }
+}
+
+/// SMBIOS chassis types that tell the form factor apart
+fn chassis_name(chassis_type: u8) -> Option<&'static str> {
+    Some(match chassis_type {
+        3 | 4 | 6 | 7 | 15 | 16 => "Desktop",
+        5 => "Pizza Box",
+        8 => "Portable",
+        9 | 10 | 14 => "Laptop",
+        11 => "Handheld",
+        13 => "All-in-One",
+        17 | 23 | 28 => "Server",
+        30 => "Tablet",
+        31 | 32 => "Convertible",
+        35 | 36 => "Mini PC",
+        _ => return None,
+    })
+}
+
+/// System batteries, sorted by name, and whether AC power is connected
+fn read_power_supplies(root: &Path) -> (Vec<Battery>, Option<bool>) {
+    let mut batteries = Vec::new();
+    let mut on_ac_power = None;
+
+    let Ok(entries) = std::fs::read_dir(root.join("sys/class/power_supply")) else {
+        return (batteries, on_ac_power);
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |file: &str| read_value(&dir.join(file));
+        let read_number = |file: &str| read(file)?.parse::<u64>().ok();
+
+        match read("type").as_deref() {
+            Some("Mains") => {
+                on_ac_power =
+                    Some(on_ac_power.unwrap_or(false) || read_number("online") == Some(1));
+            }
+            // Wireless mice and keyboards report `scope=Device`
+            Some("Battery") if read("scope").as_deref() != Some("Device") => {
+                // Batteries report either energy (µWh) or charge (µAh)
+                let full = read_number("energy_full").or_else(|| read_number("charge_full"));
+                let design =
+                    read_number("energy_full_design").or_else(|| read_number("charge_full_design"));
+
+                batteries.push(Battery {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    manufacturer: read("manufacturer"),
+                    model: read("model_name"),
+                    technology: read("technology"),
+                    status: read("status"),
+                    capacity_percent: read_number("capacity").map(|c| c.min(100) as u8),
+                    health_percent: match (full, design) {
+                        (Some(full), Some(design)) if design > 0 => {
+                            Some((full * 100 / design).min(100) as u8)
+                        }
+                        _ => None,
+                    },
+                    // Reported as 0 by firmware that does not count cycles
+                    cycle_count: read_number("cycle_count")
+                        .filter(|&cycles| cycles > 0)
+                        .map(|cycles| cycles as u32),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+    (batteries, on_ac_power)
+}
+
+/// Connected USB devices, without the virtual root hubs
+fn read_usb_devices(root: &Path) -> Vec<UsbDevice> {
+    let Ok(entries) = std::fs::read_dir(root.join("sys/bus/usb/devices")) else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<UsbDevice> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let port = entry.file_name().to_string_lossy().into_owned();
+            // `1-2:1.0` and the like are interfaces of a device
+            if port.contains(':') {
+                return None;
+            }
+            let dir = entry.path();
+            let vendor_id = read_hex(&dir.join("idVendor"))? as u16;
+            if vendor_id == USB_VENDOR_LINUX_FOUNDATION {
+                return None;
+            }
+
+            Some(UsbDevice {
+                port,
+                vendor_id,
+                product_id: read_hex(&dir.join("idProduct"))? as u16,
+                manufacturer: read_value(&dir.join("manufacturer")),
+                product: read_value(&dir.join("product")),
+            })
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a.port.cmp(&b.port));
+    devices
+}
+
+/// Every device under `root/sys/bus/pci/devices`, sorted by slot
+pub(crate) fn read_pci_devices(root: &Path) -> Vec<PciDevice> {
+    let Ok(entries) = std::fs::read_dir(root.join("sys/bus/pci/devices")) else {
+        return Vec::new();
+    };
+    let pci_ids = read_pci_ids(root);
+
+    let mut devices: Vec<PciDevice> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let vendor_id = read_hex(&dir.join("vendor"))? as u16;
+            let device_id = read_hex(&dir.join("device"))? as u16;
+
+            Some(PciDevice {
+                address: entry.file_name().to_string_lossy().into_owned(),
+                class: (read_hex(&dir.join("class"))? >> 8) as u16,
+                vendor_id,
+                device_id,
+                vendor: pci_ids
+                    .as_deref()
+                    .and_then(|ids| lookup_pci_vendor(ids, vendor_id)),
+                model: pci_ids
+                    .as_deref()
+                    .and_then(|ids| lookup_pci_device(ids, vendor_id, device_id)),
+                driver: std::fs::read_link(dir.join("driver"))
+                    .ok()
+                    .and_then(|target| Some(target.file_name()?.to_string_lossy().into_owned())),
+            })
+        })
+        .collect();
+
+    devices.sort_by(|a, b| a.address.cmp(&b.address));
+    devices
+}
+
+/// Contents of the first `pci.ids` database found under `root`
+fn read_pci_ids(root: &Path) -> Option<String> {
+    PCI_IDS_PATHS
+        .iter()
+        .find_map(|path| std::fs::read_to_string(root.join(path)).ok())
+}
+
+/// Find a vendor name in a `pci.ids` database
+fn lookup_pci_vendor(pci_ids: &str, vendor_id: u16) -> Option<String> {
+    let vendor = format!("{:04x}", vendor_id);
+
+    pci_ids.lines().find_map(|line| {
+        let (id, name) = line.split_once("  ")?;
+        (id == vendor).then(|| name.trim().to_string())
+    })
+}
+
+/// Find a device name in a `pci.ids` database
+fn lookup_pci_device(pci_ids: &str, vendor_id: u16, device_id: u16) -> Option<String> {
+    let vendor = format!("{:04x}", vendor_id);
+    let device = format!("{:04x}", device_id);
+
+    pci_ids
+        .lines()
+        .skip_while(|line| !line.starts_with(&vendor))
+        .skip(1)
+        // Devices are indented by one tab; the next vendor ends the section
+        .take_while(|line| line.starts_with('\t') || line.starts_with('#') || line.is_empty())
+        .filter(|line| !line.starts_with("\t\t"))
+        .find_map(|line| {
+            let (id, name) = line.trim_start_matches('\t').split_once("  ")?;
+            (id == device).then(|| name.trim().to_string())
+        })
+}
+
+/// Trimmed contents of a sysfs attribute; `None` when missing or empty
+fn read_value(path: &Path) -> Option<String> {
+    let value = std::fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn read_hex(path: &Path) -> Option<u32> {
+    u32::from_str_radix(read_value(path)?.trim_start_matches("0x"), 16).ok()
+}
+
+/// Space separated values that are present, or `None` if there are none
+fn join_some(values: &[&Option<String>]) -> Option<String> {
+    let parts: Vec<&str> = values.iter().filter_map(|value| value.as_deref()).collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::FakeRoot;
+
+    /// Write sysfs attributes into `dir`, creating it
+    fn write_attributes(dir: &Path, attributes: &[(&str, &str)]) {
+        std::fs::create_dir_all(dir).unwrap();
+        for (name, value) in attributes {
+            std::fs::write(dir.join(name), format!("{}\n", value)).unwrap();
+        }
+    }
+
+    const PCI_IDS: &str = "\
+# pci.ids excerpt
+10de  NVIDIA Corporation
+\t2684  AD102 [GeForce RTX 4090]
+\t\t1043 889d  ROG Strix RTX 4090
+\t2782  AD104 [GeForce RTX 4070 Ti]
+8086  Intel Corporation
+\t9a49  TigerLake-LP GT2 [Iris Xe Graphics]
+";
+
+    #[test]
+    fn test_read_dmi_skips_placeholders() {
+        let root = FakeRoot::new("hardware-dmi");
+        write_attributes(
+            &root.join("sys/class/dmi/id"),
+            &[
+                ("sys_vendor", "LENOVO"),
+                ("product_name", "21AH00BPGE"),
+                ("product_version", "ThinkPad T14 Gen 3"),
+                ("chassis_type", "10"),
+                ("board_vendor", "LENOVO"),
+                ("board_name", "21AH00BPGE"),
+                ("board_version", "To Be Filled By O.E.M."),
+                ("bios_vendor", "LENOVO"),
+                ("bios_version", "N3MET18W (1.17 )"),
+                ("bios_date", "02/06/2024"),
+            ],
+        );
+
+        let dmi = HardwareInfo::detect_in(&root).dmi;
+        assert_eq!(
+            dmi.model().as_deref(),
+            Some("LENOVO 21AH00BPGE ThinkPad T14 Gen 3")
+        );
+        assert_eq!(dmi.chassis.as_deref(), Some("Laptop"));
+        assert_eq!(dmi.board_version, None);
+        assert_eq!(dmi.board().as_deref(), Some("LENOVO 21AH00BPGE"));
+        assert_eq!(
+            dmi.bios().as_deref(),
+            Some("LENOVO N3MET18W (1.17 ) (02/06/2024)")
+        );
+
+        assert_eq!(
+            HardwareInfo::detect_in(&root.join("missing")),
+            HardwareInfo::default()
+        );
+    }
+
+    #[test]
+    fn test_read_power_supplies() {
+        let root = FakeRoot::new("hardware-power");
+        let supplies = root.join("sys/class/power_supply");
+        write_attributes(
+            &supplies.join("BAT0"),
+            &[
+                ("type", "Battery"),
+                ("scope", "System"),
+                ("manufacturer", "SMP"),
+                ("model_name", "5B10W51867"),
+                ("technology", "Li-poly"),
+                ("status", "Discharging"),
+                ("capacity", "76"),
+                ("energy_full", "45120000"),
+                ("energy_full_design", "52500000"),
+                ("cycle_count", "0"),
+            ],
+        );
+        write_attributes(
+            &supplies.join("BAT1"),
+            &[
+                ("type", "Battery"),
+                ("capacity", "100"),
+                ("charge_full", "4000000"),
+                ("charge_full_design", "4000000"),
+                ("cycle_count", "213"),
+            ],
+        );
+        write_attributes(&supplies.join("AC"), &[("type", "Mains"), ("online", "0")]);
+        write_attributes(
+            &supplies.join("hidpp_battery_0"),
+            &[("type", "Battery"), ("scope", "Device"), ("capacity", "40")],
+        );
+
+        let (batteries, on_ac_power) = read_power_supplies(&root);
+        assert_eq!(on_ac_power, Some(false));
+        assert_eq!(batteries.len(), 2);
+        assert_eq!(batteries[0].name, "BAT0");
+        assert_eq!(batteries[0].health_percent, Some(85));
+        assert_eq!(batteries[0].cycle_count, None);
+        assert_eq!(batteries[0].summary(), "76%, discharging, health 85%");
+        assert_eq!(batteries[1].health_percent, Some(100));
+        assert_eq!(batteries[1].cycle_count, Some(213));
+    }
+
+    #[test]
+    fn test_read_usb_and_pci_devices() {
+        let root = FakeRoot::new("hardware-devices");
+        let usb = root.join("sys/bus/usb/devices");
+        write_attributes(
+            &usb.join("usb1"),
+            &[("idVendor", "1d6b"), ("idProduct", "0002")],
+        );
+        write_attributes(
+            &usb.join("1-2"),
+            &[
+                ("idVendor", "046d"),
+                ("idProduct", "c52b"),
+                ("manufacturer", "Logitech"),
+                ("product", "USB Receiver"),
+            ],
+        );
+        write_attributes(&usb.join("1-2:1.0"), &[("bInterfaceClass", "03")]);
+        write_attributes(
+            &usb.join("1-4"),
+            &[("idVendor", "0bda"), ("idProduct", "5411")],
+        );
+
+        write_attributes(
+            &root.join("sys/bus/pci/devices/0000:00:02.0"),
+            &[
+                ("class", "0x030000"),
+                ("vendor", "0x8086"),
+                ("device", "0x9a49"),
+            ],
+        );
+        std::fs::create_dir_all(root.join("usr/share/hwdata")).unwrap();
+        std::fs::write(root.join("usr/share/hwdata/pci.ids"), PCI_IDS).unwrap();
+
+        let info = HardwareInfo::detect_in(&root);
+        assert_eq!(info.usb_devices.len(), 2);
+        assert_eq!(info.usb_devices[0].name(), "Logitech USB Receiver");
+        assert_eq!(info.usb_devices[1].name(), "[0bda:5411]");
+
+        assert_eq!(info.pci_devices.len(), 1);
+        let gpu = &info.pci_devices[0];
+        assert_eq!(gpu.class, 0x0300);
+        assert_eq!(gpu.class_name(), "Display controller");
+        assert_eq!(
+            gpu.name(),
+            "Intel Corporation TigerLake-LP GT2 [Iris Xe Graphics]"
+        );
+
+        let text = info.to_text();
+        assert!(text.contains("Model: Unknown\n"));
+        assert!(text.contains("  046d:c52b Logitech USB Receiver\n"));
+        assert!(text.contains("  0000:00:02.0 Display controller: Intel Corporation"));
+    }
+
+    #[test]
+    fn test_lookup_pci_device_stays_in_vendor_section() {
+        assert_eq!(
+            lookup_pci_device(PCI_IDS, 0x10de, 0x2782).as_deref(),
+            Some("AD104 [GeForce RTX 4070 Ti]")
+        );
+        // Intel's 9a49 must not match under NVIDIA
+        assert_eq!(lookup_pci_device(PCI_IDS, 0x10de, 0x9a49), None);
+        assert_eq!(
+            lookup_pci_vendor(PCI_IDS, 0x8086).as_deref(),
+            Some("Intel Corporation")
+        );
+    }
+}
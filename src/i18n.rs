@@ -11,6 +11,11 @@ pub struct Translations {
     pub update: UpdateTranslations,
     pub drivers: DriversTranslations,
     pub software: SoftwareTranslations,
+    pub dashboard: DashboardTranslations,
+    pub health: HealthTranslations,
+    pub services: ServicesTranslations,
+    pub boot: BootTranslations,
+    pub hardware: HardwareTranslations,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +31,11 @@ pub struct SidebarTranslations {
     pub update: String,
     pub drivers: String,
     pub software: String,
+    pub dashboard: String,
+    pub health: String,
+    pub services: String,
+    pub boot: String,
+    pub hardware: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +76,11 @@ pub struct DriversTranslations {
     pub driver_current: String,
     pub btn_launch: String,
     pub session_type: String,
+    pub proprietary_driver: String,
+    pub recommended_via: String,
+    pub install_success: String,
+    pub install_failed: String,
+    pub settings_failed: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +88,82 @@ pub struct SoftwareTranslations {
     pub title: String,
     pub description: String,
     pub btn_install: String,
+    /// Category labels keyed by id (`web`, `editor`, `music`, ...)
+    #[serde(default)]
+    pub categories: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardTranslations {
+    pub cpu: String,
+    pub core: String,
+    pub memory: String,
+    pub swap: String,
+    pub no_swap: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthTranslations {
+    pub check_again: String,
+    pub checking: String,
+    pub no_problems: String,
+    pub attention: String,
+    pub critical: String,
+    pub fix_failed: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicesTranslations {
+    pub refresh: String,
+    pub loading: String,
+    pub failed_title: String,
+    pub no_failed: String,
+    pub failed_error: String,
+    pub user_unit: String,
+    pub errors_title: String,
+    pub errors_description: String,
+    pub no_errors: String,
+    pub journal_error: String,
+    pub restart: String,
+    pub restarted: String,
+    pub restart_failed: String,
+    pub view_log: String,
+    pub no_log: String,
+    pub log_error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootTranslations {
+    pub refresh: String,
+    pub analyzing: String,
+    pub this_boot: String,
+    pub unavailable: String,
+    pub regression: String,
+    pub usually: String,
+    pub usually_kernel_changed: String,
+    pub total: String,
+    pub slowest_units: String,
+    pub slowest_units_description: String,
+    pub history: String,
+    pub history_entry: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardwareTranslations {
+    pub copy: String,
+    pub copied: String,
+    pub computer: String,
+    pub model: String,
+    pub chassis: String,
+    pub motherboard: String,
+    pub bios: String,
+    pub power: String,
+    pub ac_adapter: String,
+    pub connected: String,
+    pub disconnected: String,
+    pub usb_devices: String,
+    pub no_usb: String,
+    pub pci_devices: String,
 }
 
 lazy_static! {
@@ -217,6 +308,11 @@ fn get_fallback_en() -> Translations {
             update: "Update System".to_string(),
             drivers: "Drivers".to_string(),
             software: "Software".to_string(),
+            dashboard: "Dashboard".to_string(),
+            health: "Health".to_string(),
+            services: "Services".to_string(),
+            boot: "Boot".to_string(),
+            hardware: "Hardware".to_string(),
         },
         home: HomeTranslations {
             title: "Welcome to Your System".to_string(),
@@ -251,11 +347,95 @@ fn get_fallback_en() -> Translations {
             driver_current: "Active".to_string(),
             btn_launch: "Open Settings".to_string(),
             session_type: "Session Type".to_string(),
+            proprietary_driver: "Proprietary NVIDIA driver".to_string(),
+            recommended_via: "Recommended via {tool}".to_string(),
+            install_success: "Driver installed. Restart to start using it.".to_string(),
+            install_failed: "Driver installation failed".to_string(),
+            settings_failed: "Failed to open NVIDIA Settings".to_string(),
         },
         software: SoftwareTranslations {
             title: "Recommended Software".to_string(),
             description: "Install popular applications to get started quickly".to_string(),
             btn_install: "Install".to_string(),
+            categories: [
+                ("web", "Web Browser"),
+                ("editor", "Code Editor"),
+                ("music", "Music"),
+                ("chat", "Communication"),
+                ("graphics", "Graphics"),
+                ("media", "Media"),
+            ]
+            .into_iter()
+            .map(|(id, label)| (id.to_string(), label.to_string()))
+            .collect(),
+        },
+        dashboard: DashboardTranslations {
+            cpu: "CPU".to_string(),
+            core: "Core {core}".to_string(),
+            memory: "Memory".to_string(),
+            swap: "Swap".to_string(),
+            no_swap: "No swap".to_string(),
+        },
+        health: HealthTranslations {
+            check_again: "Check Again".to_string(),
+            checking: "Checking your system…".to_string(),
+            no_problems: "No problems found.".to_string(),
+            attention: "Some things need your attention.".to_string(),
+            critical: "Problems found that can break your system.".to_string(),
+            fix_failed: "The fix did not succeed".to_string(),
+        },
+        services: ServicesTranslations {
+            refresh: "Refresh".to_string(),
+            loading: "Loading…".to_string(),
+            failed_title: "Failed Services".to_string(),
+            no_failed: "No services have failed.".to_string(),
+            failed_error: "Could not list failed services.".to_string(),
+            user_unit: "user".to_string(),
+            errors_title: "Recent Errors".to_string(),
+            errors_description: "Errors logged since boot, newest last.".to_string(),
+            no_errors: "No errors logged since boot.".to_string(),
+            journal_error: "Could not read the system journal.".to_string(),
+            restart: "Restart".to_string(),
+            restarted: "Restarted {unit}".to_string(),
+            restart_failed: "Could not restart {unit}".to_string(),
+            view_log: "View Log".to_string(),
+            no_log: "No log entries since boot.".to_string(),
+            log_error: "Could not read the log: {error}".to_string(),
+        },
+        boot: BootTranslations {
+            refresh: "Refresh".to_string(),
+            analyzing: "Analyzing the boot…".to_string(),
+            this_boot: "This Boot".to_string(),
+            unavailable: "Boot times are available once the system has finished starting up."
+                .to_string(),
+            regression: "Booting took {time} longer than usual".to_string(),
+            usually: "Usually {time}".to_string(),
+            usually_kernel_changed:
+                "Usually {time}. The kernel changed since the previous boot (was {kernel})."
+                    .to_string(),
+            total: "Total".to_string(),
+            slowest_units: "Slowest Units".to_string(),
+            slowest_units_description:
+                "Units start in parallel, so their times overlap and add up to more than the total."
+                    .to_string(),
+            history: "Boot History".to_string(),
+            history_entry: "{time} · kernel {kernel}".to_string(),
+        },
+        hardware: HardwareTranslations {
+            copy: "Copy to Clipboard".to_string(),
+            copied: "Copied".to_string(),
+            computer: "Computer".to_string(),
+            model: "Model".to_string(),
+            chassis: "Type".to_string(),
+            motherboard: "Motherboard".to_string(),
+            bios: "BIOS".to_string(),
+            power: "Power".to_string(),
+            ac_adapter: "AC Adapter".to_string(),
+            connected: "Connected".to_string(),
+            disconnected: "Disconnected".to_string(),
+            usb_devices: "USB Devices".to_string(),
+            no_usb: "No USB devices connected".to_string(),
+            pci_devices: "PCI Devices".to_string(),
         },
     }
 }
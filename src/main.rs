@@ -1,18 +1,33 @@
 //! Ro-Start — Fast, safe, and beautiful Linux welcome application
 //! Built with Rust + GTK4 + libadwaita
 
+mod autostart;
+mod boot;
+mod cli;
 mod config;
+mod daemon;
+mod doctor;
+mod drivers;
 mod error;
+mod hardware;
 mod i18n;
 mod notifications;
+mod os_release;
+mod output;
 mod package_manager;
+mod report;
+mod runtime;
+mod services;
 mod system;
+#[cfg(test)]
+mod test_util;
 mod ui;
 
 use clap::Parser;
 use gio::prelude::*;
 use gtk::prelude::*;
 use gtk::Application;
+use std::process::ExitCode;
 
 const APP_ID: &str = "org.osdev.rostart";
 
@@ -21,7 +36,8 @@ const APP_ID: &str = "org.osdev.rostart";
 #[command(version = "2.0.0")]
 #[command(about = "Fast, safe, and beautiful Linux welcome application", long_about = None)]
 struct Cli {
-    /// Don't show at startup
+    /// Launched from the login autostart entry; exit quietly when launching
+    /// at login is disabled
     #[arg(long)]
     no_startup: bool,
 
@@ -29,22 +45,41 @@ struct Cli {
     #[arg(long)]
     locale: Option<String>,
 
+    /// Open a page of the main window, in the running instance if there is one
+    #[arg(long, value_enum)]
+    page: Option<ui::Page>,
+
+    /// Open the update page and start installing updates right away
+    #[arg(long)]
+    update_now: bool,
+
+    /// Check for updates in the background and notify, without opening a window
+    #[arg(long)]
+    daemon: bool,
+
     /// Enable debug logging
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     debug: bool,
+
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 }
 
-fn main() {
+fn main() -> ExitCode {
     // Parse CLI arguments
     let cli = Cli::parse();
 
     // Initialize tracing
-    let log_level = if cli.debug {
-        "ro_start=debug"
-    } else {
-        "ro_start=info"
+    let log_level = match (cli.debug, &cli.command) {
+        (true, _) => "ro_start=debug",
+        // Keep headless output clean for scripts
+        (false, Some(_)) => "ro_start=warn",
+        (false, None) => "ro_start=info",
     };
-    tracing_subscriber::fmt().with_env_filter(log_level).init();
+    tracing_subscriber::fmt()
+        .with_env_filter(log_level)
+        .with_writer(std::io::stderr)
+        .init();
 
     tracing::info!("🚀 Starting Ro-Start v2.0.0");
 
@@ -60,13 +95,63 @@ fn main() {
 
     tracing::info!("📖 Locale: {}", i18n::get_locale());
 
+    if let Some(command) = cli.command {
+        return cli::run(command);
+    }
+
+    if cli.no_startup {
+        let config = config::AppConfig::load().unwrap_or_default();
+        if !config.autostart {
+            tracing::info!("Launch at login is disabled, exiting");
+            return ExitCode::SUCCESS;
+        }
+        // At login, only greet on first run or after an upgrade
+        let distro = system::distro_release();
+        if config
+            .welcome_reason(env!("CARGO_PKG_VERSION"), &distro)
+            .is_none()
+        {
+            tracing::info!("Nothing new since the last welcome, exiting");
+            return ExitCode::SUCCESS;
+        }
+    }
+
+    if cli.daemon {
+        daemon::run();
+        return ExitCode::SUCCESS;
+    }
+
     // Create GTK application
     let app = Application::builder().application_id(APP_ID).build();
 
+    ui::setup_app_actions(&app);
     app.connect_activate(build_ui);
 
-    // Run the application
-    app.run();
+    let deep_link = match (cli.update_now, cli.page) {
+        (true, _) => Some(("update-now", None)),
+        (false, Some(page)) => Some(("show-page", Some(page.name().to_variant()))),
+        (false, None) => None,
+    };
+    if let Some((action, target)) = deep_link {
+        if let Err(e) = app.register(gio::Cancellable::NONE) {
+            tracing::warn!("Failed to register application: {}", e);
+        }
+
+        // Forward the request to the instance that is already running
+        if app.is_remote() {
+            app.activate_action(action, target.as_ref());
+            if let Some(connection) = app.dbus_connection() {
+                let _ = connection.flush_sync(gio::Cancellable::NONE);
+            }
+            return ExitCode::SUCCESS;
+        }
+
+        // Otherwise open the page once our own window exists
+        app.connect_activate(move |app| app.activate_action(action, target.as_ref()));
+    }
+
+    // Command line arguments are handled by clap, not GApplication
+    ExitCode::from(app.run_with_args::<&str>(&[]).value() as u8)
 }
 
 fn build_ui(app: &Application) {
@@ -76,5 +161,43 @@ fn build_ui(app: &Application) {
     // Present window
     window.present();
 
+    // Release notes after an upgrade of Ro-Start or the distribution
+    ui::whats_new::show_if_needed(window.upcast_ref());
+
     tracing::info!("✅ Application window created");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_cli_parses_subcommands() {
+        let cli = Cli::parse_from(["ro-start", "config", "set", "autostart", "true", "-d"]);
+        assert!(cli.debug);
+        assert!(matches!(
+            cli.command,
+            Some(cli::Command::Config {
+                action: cli::ConfigAction::Set { .. }
+            })
+        ));
+
+        let cli = Cli::parse_from(["ro-start", "check-updates", "--format", "json"]);
+        assert!(matches!(
+            cli.command,
+            Some(cli::Command::CheckUpdates {
+                format: output::OutputFormat::Json
+            })
+        ));
+
+        let cli = Cli::parse_from(["ro-start", "--page", "update"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.page, Some(ui::Page::Update));
+    }
+}
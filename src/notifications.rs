@@ -1,4 +1,4 @@
-use notify_rust::Notification;
+use notify_rust::{Notification, Urgency};
 
 /// Show a desktop notification
 #[allow(dead_code)]
@@ -14,12 +14,101 @@ pub fn show_notification(title: &str, body: &str) {
     }
 }
 
-/// Show update notification
-#[allow(dead_code)]
-pub fn notify_updates_available(count: usize) {
-    let title = "Updates Available";
-    let body = format!("{} update(s) are ready to install", count);
-    show_notification(title, &body);
+/// Buttons offered on the "updates available" notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateAction {
+    UpdateNow,
+    ViewDetails,
+    RemindTomorrow,
+}
+
+impl UpdateAction {
+    const ALL: [Self; 3] = [Self::UpdateNow, Self::ViewDetails, Self::RemindTomorrow];
+
+    fn id(self) -> &'static str {
+        match self {
+            Self::UpdateNow => "update-now",
+            Self::ViewDetails => "view-details",
+            Self::RemindTomorrow => "remind-tomorrow",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::UpdateNow => "Update now",
+            Self::ViewDetails => "View details",
+            Self::RemindTomorrow => "Remind me tomorrow",
+        }
+    }
+
+    /// Map the action key reported by the notification server. Clicking the
+    /// notification body sends `default`, which opens the details.
+    fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "default" => Some(Self::ViewDetails),
+            _ => Self::ALL.into_iter().find(|action| action.id() == id),
+        }
+    }
+}
+
+/// Show update notification. Security updates use critical urgency so the
+/// notification stays on screen until it is dismissed.
+///
+/// `on_action` runs on a helper thread when the user picks one of the
+/// notification's buttons; it is not called when the notification is closed or
+/// the notification server does not support actions.
+pub fn notify_updates_available(
+    count: usize,
+    security_count: usize,
+    on_action: impl FnOnce(UpdateAction) + Send + 'static,
+) {
+    let mut notification = Notification::new();
+    if security_count == 0 {
+        notification
+            .summary("Updates Available")
+            .body(&format!("{} update(s) are ready to install", count))
+            .icon("ro-start");
+    } else {
+        notification
+            .summary("Security Updates Available")
+            .body(&format!(
+                "{} of {} update(s) fix security issues and should be installed soon",
+                security_count, count
+            ))
+            .icon("security-high")
+            .urgency(Urgency::Critical);
+    }
+
+    let supports_actions = notify_rust::get_capabilities()
+        .map(|caps| caps.iter().any(|cap| cap == "actions"))
+        .unwrap_or(false);
+    if supports_actions {
+        notification.action("default", "View details");
+        for action in UpdateAction::ALL {
+            notification.action(action.id(), action.label());
+        }
+    }
+
+    let handle = match notification.show() {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::warn!("Failed to show notification: {}", e);
+            return;
+        }
+    };
+
+    if supports_actions {
+        // wait_for_action blocks until the notification is acted upon or closed
+        std::thread::spawn(move || {
+            handle.wait_for_action(|id| match UpdateAction::from_id(id) {
+                Some(action) => {
+                    tracing::info!("Notification action: {:?}", action);
+                    on_action(action);
+                }
+                None => tracing::debug!("Notification closed ({})", id),
+            });
+        });
+    }
 }
 
 /// Show success notification
@@ -31,3 +120,20 @@ pub fn notify_success(message: &str) {
 pub fn notify_error(message: &str) {
     show_notification("Error", message);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_action_ids_roundtrip() {
+        for action in UpdateAction::ALL {
+            assert_eq!(UpdateAction::from_id(action.id()), Some(action));
+        }
+        assert_eq!(
+            UpdateAction::from_id("default"),
+            Some(UpdateAction::ViewDetails)
+        );
+        assert_eq!(UpdateAction::from_id("__closed"), None);
+    }
+}
@@ -0,0 +1,175 @@
+//! `/etc/os-release` parser
+//!
+//! See os-release(5). Values may be quoted with single or double quotes;
+//! backslash escapes are honoured inside double quotes.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Locations of the os-release file, relative to the root, in lookup order
+const OS_RELEASE_PATHS: &[&str] = &["etc/os-release", "usr/lib/os-release"];
+
+/// Distribution identity from os-release
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OsRelease {
+    pub name: Option<String>,
+    pub pretty_name: Option<String>,
+    /// Lower-case identifier, e.g. `ubuntu`
+    pub id: Option<String>,
+    /// Identifiers of the distributions this one derives from, closest first
+    pub id_like: Vec<String>,
+    pub version: Option<String>,
+    pub version_id: Option<String>,
+    pub version_codename: Option<String>,
+    pub home_url: Option<String>,
+    pub documentation_url: Option<String>,
+    pub support_url: Option<String>,
+    pub bug_report_url: Option<String>,
+    /// Icon name of the distribution logo
+    pub logo: Option<String>,
+}
+
+impl OsRelease {
+    /// Read the os-release file of the running system
+    pub fn load() -> Self {
+        Self::load_from(Path::new("/"))
+    }
+
+    /// Read the os-release file under `root`; empty when there is none
+    pub fn load_from(root: &Path) -> Self {
+        OS_RELEASE_PATHS
+            .iter()
+            .find_map(|path| std::fs::read_to_string(root.join(path)).ok())
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let mut fields: HashMap<&str, String> = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), unquote(value.trim())))
+            .collect();
+        let mut take = |key: &str| fields.remove(key).filter(|value| !value.is_empty());
+
+        Self {
+            name: take("NAME"),
+            pretty_name: take("PRETTY_NAME"),
+            id: take("ID"),
+            id_like: take("ID_LIKE")
+                .map(|ids| ids.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            version: take("VERSION"),
+            version_id: take("VERSION_ID"),
+            version_codename: take("VERSION_CODENAME"),
+            home_url: take("HOME_URL"),
+            documentation_url: take("DOCUMENTATION_URL"),
+            support_url: take("SUPPORT_URL"),
+            bug_report_url: take("BUG_REPORT_URL"),
+            logo: take("LOGO"),
+        }
+    }
+
+    /// `ID` followed by `ID_LIKE`, the order in which to match distribution
+    /// specific behaviour
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.id
+            .iter()
+            .chain(self.id_like.iter())
+            .map(String::as_str)
+    }
+}
+
+fn unquote(value: &str) -> String {
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return inner.to_string();
+    }
+    let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        return value.to_string();
+    };
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('"' | '\\' | '$' | '`'))) => {
+                unquoted.push(next);
+                chars.next();
+            }
+            _ => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::FakeRoot;
+
+    const UBUNTU: &str = r#"PRETTY_NAME="Ubuntu 24.04.1 LTS"
+NAME="Ubuntu"
+VERSION_ID="24.04"
+VERSION="24.04.1 LTS (Noble Numbat)"
+VERSION_CODENAME=noble
+ID=ubuntu
+ID_LIKE=debian
+HOME_URL="https://www.ubuntu.com/"
+SUPPORT_URL="https://help.ubuntu.com/"
+BUG_REPORT_URL="https://bugs.launchpad.net/ubuntu/"
+PRIVACY_POLICY_URL="https://www.ubuntu.com/legal/terms-and-policies/privacy-policy"
+UBUNTU_CODENAME=noble
+LOGO=ubuntu-logo
+"#;
+
+    #[test]
+    fn test_parse_ubuntu() {
+        let os = OsRelease::parse(UBUNTU);
+        assert_eq!(os.name.as_deref(), Some("Ubuntu"));
+        assert_eq!(os.pretty_name.as_deref(), Some("Ubuntu 24.04.1 LTS"));
+        assert_eq!(os.id.as_deref(), Some("ubuntu"));
+        assert_eq!(os.id_like, ["debian"]);
+        assert_eq!(os.version_id.as_deref(), Some("24.04"));
+        assert_eq!(os.version_codename.as_deref(), Some("noble"));
+        assert_eq!(os.home_url.as_deref(), Some("https://www.ubuntu.com/"));
+        assert_eq!(os.documentation_url, None);
+        assert_eq!(os.logo.as_deref(), Some("ubuntu-logo"));
+        assert_eq!(os.ids().collect::<Vec<_>>(), ["ubuntu", "debian"]);
+    }
+
+    #[test]
+    fn test_parse_quoting_and_comments() {
+        let os = OsRelease::parse(
+            "# comment\n\
+             NAME='Linux Mint'\n\
+             ID=linuxmint\n\
+             ID_LIKE=\"ubuntu debian\"\n\
+             PRETTY_NAME=\"Say \\\"hi\\\" \\\\ \\$HOME\"\n\
+             VERSION_ID=\"\"\n",
+        );
+        assert_eq!(os.name.as_deref(), Some("Linux Mint"));
+        assert_eq!(os.id_like, ["ubuntu", "debian"]);
+        assert_eq!(os.pretty_name.as_deref(), Some(r#"Say "hi" \ $HOME"#));
+        assert_eq!(os.version_id, None);
+    }
+
+    #[test]
+    fn test_load_falls_back_to_usr_lib() {
+        let root = FakeRoot::new("os-release");
+        std::fs::create_dir_all(root.join("usr/lib")).unwrap();
+        std::fs::write(
+            root.join("usr/lib/os-release"),
+            "ID=arch\nNAME=\"Arch Linux\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(OsRelease::load_from(&root).id.as_deref(), Some("arch"));
+        assert_eq!(
+            OsRelease::load_from(&root.join("missing")),
+            OsRelease::default()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Machine-readable output for the headless subcommands
+//!
+//! JSON and TOML documents share one envelope:
+//!
+//! ```json
+//! { "schema_version": 1, "system": { ... } }
+//! ```
+//!
+//! `schema_version` is bumped whenever a field is renamed or removed or its
+//! meaning changes. Adding fields does not bump it.
+
+use serde::Serialize;
+
+/// Version of the JSON/TOML document layout
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    Json,
+    Toml,
+}
+
+/// Versioned envelope holding one top-level section, e.g. `system` or `updates`
+#[derive(Serialize)]
+struct Document<'a, T: Serialize> {
+    schema_version: u32,
+    #[serde(flatten)]
+    section: Section<'a, T>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Section<'a, T: Serialize> {
+    System(&'a T),
+    Updates(&'a T),
+    Health(&'a T),
+}
+
+/// The `health` section: overall status and every check result
+#[derive(Serialize)]
+struct Health<'a> {
+    status: crate::doctor::Severity,
+    checks: &'a [crate::doctor::CheckResult],
+}
+
+/// Serialize `info` as the `system` section
+pub fn system_document(
+    info: &crate::system::SystemInfo,
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    render(Section::System(info), format)
+}
+
+/// Serialize `info` as the `updates` section
+pub fn updates_document(
+    info: &crate::package_manager::UpdateInfo,
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    render(Section::Updates(info), format)
+}
+
+/// Serialize health check `results` as the `health` section
+pub fn health_document(
+    results: &[crate::doctor::CheckResult],
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    let health = Health {
+        status: crate::doctor::overall_severity(results),
+        checks: results,
+    };
+    render(Section::Health(&health), format)
+}
+
+fn render<T: Serialize>(section: Section<'_, T>, format: OutputFormat) -> anyhow::Result<String> {
+    let document = Document {
+        schema_version: SCHEMA_VERSION,
+        section,
+    };
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&document)? + "\n"),
+        OutputFormat::Toml => Ok(toml::to_string_pretty(&document)?),
+        OutputFormat::Text => anyhow::bail!("Text output is rendered by the caller"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::{PackageManager, SourceUpdates, UpdateInfo};
+
+    fn sample_updates() -> UpdateInfo {
+        let apt = PackageManager::Apt;
+        let updates = apt.parse_updates(include_str!(
+            "package_manager/fixtures/apt-list-upgradable.txt"
+        ));
+        UpdateInfo::from_sources(vec![SourceUpdates {
+            package_manager: apt,
+            updates,
+        }])
+    }
+
+    #[test]
+    fn test_updates_json_schema() {
+        let json = updates_document(&sample_updates(), OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["updates"]["count"], 3);
+        assert_eq!(value["updates"]["security_count"], 2);
+        let source = &value["updates"]["sources"][0];
+        assert_eq!(source["package_manager"], "apt");
+        assert_eq!(source["updates"][0]["name"], "firefox");
+        assert_eq!(source["updates"][0]["category"], "security");
+        assert_eq!(source["updates"][0]["severity"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_updates_toml_schema() {
+        let toml = updates_document(&sample_updates(), OutputFormat::Toml).unwrap();
+        let value: toml::Table = toml::from_str(&toml).unwrap();
+
+        assert_eq!(
+            value["schema_version"].as_integer(),
+            Some(SCHEMA_VERSION.into())
+        );
+        assert_eq!(
+            value["updates"]["sources"][0]["updates"][1]["name"].as_str(),
+            Some("libc6")
+        );
+    }
+
+    #[test]
+    fn test_health_json_schema() {
+        use crate::doctor::{Check, CheckResult, Severity};
+
+        let results = [CheckResult {
+            check: Check::ClockSync,
+            severity: Severity::Warning,
+            summary: "Network time synchronization is turned off".to_string(),
+            details: Vec::new(),
+            fix: None,
+        }];
+        let json = health_document(&results, OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["health"]["status"], "warning");
+        assert_eq!(value["health"]["checks"][0]["check"], "clock-sync");
+        assert_eq!(value["health"]["checks"][0]["fix"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_system_json_schema() {
+        let info = crate::system::SystemState::new().get_system_info();
+        let json = system_document(&info, OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let system = &value["system"];
+        assert!(system["total_memory_bytes"].as_u64().unwrap() > 0);
+        assert!(system["kernel_version"].is_string());
+        // Display-only strings are not part of the schema
+        assert!(system.get("memory_info").is_none());
+        assert!(system.get("cpu_info").is_none());
+        assert!(system["disks"].is_array());
+
+        let toml = system_document(&info, OutputFormat::Toml).unwrap();
+        assert!(toml::from_str::<toml::Table>(&toml).is_ok());
+    }
+}
@@ -0,0 +1,1249 @@
+mod parsers;
+mod restart;
+pub mod runner;
+
+pub use restart::RestartStatus;
+
+use crate::error::{Result, RoStartError};
+use crate::os_release::OsRelease;
+use parsers::*;
+use runner::{CommandOutput, CommandRunner, SystemRunner};
+use serde::Serialize;
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+pub enum PackageManager {
+    Apt,
+    Dnf,
+    Pacman,
+    Zypper,
+    Apk,
+    Xbps,
+    Emerge,
+    Eopkg,
+    Nix,
+    // Secondary sources that live alongside the native package manager
+    Flatpak,
+    Snap,
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Apt => write!(f, "apt"),
+            Self::Dnf => write!(f, "dnf"),
+            Self::Pacman => write!(f, "pacman"),
+            Self::Zypper => write!(f, "zypper"),
+            Self::Apk => write!(f, "apk"),
+            Self::Xbps => write!(f, "xbps"),
+            Self::Emerge => write!(f, "emerge"),
+            Self::Eopkg => write!(f, "eopkg"),
+            Self::Nix => write!(f, "nix"),
+            Self::Flatpak => write!(f, "flatpak"),
+            Self::Snap => write!(f, "snap"),
+        }
+    }
+}
+
+impl PackageManager {
+    /// Detect the system's package manager by checking for working binaries
+    #[allow(dead_code)]
+    pub fn detect() -> Result<Self> {
+        Self::detect_with(&SystemRunner, &OsRelease::load())
+    }
+
+    /// Detect the system's package manager using `runner` to probe binaries.
+    ///
+    /// The backends of the distribution's `ID` and `ID_LIKE` are tried first;
+    /// the remaining ones are probed in a fixed order.
+    pub fn detect_with(runner: &impl CommandRunner, os_release: &OsRelease) -> Result<Self> {
+        for pm in os_release.ids().filter_map(Self::for_distro_id) {
+            if Self::binary_works(runner, pm.binary()) {
+                tracing::debug!("Detected package manager from os-release: {}", pm);
+                return Ok(pm);
+            }
+        }
+
+        let candidates = [
+            Self::Apt,
+            Self::Dnf,
+            Self::Pacman,
+            Self::Zypper,
+            Self::Apk,
+            Self::Xbps,
+            Self::Emerge,
+            Self::Eopkg,
+            // Nix can be installed on top of any distribution, so it is probed last
+            Self::Nix,
+        ];
+
+        for pm in candidates {
+            if Self::binary_works(runner, pm.binary()) {
+                tracing::debug!("Detected package manager: {}", pm);
+                return Ok(pm);
+            }
+        }
+
+        Err(RoStartError::PackageManagerNotFound)
+    }
+
+    /// Detect every update source on the system: the native package manager
+    /// (if any) followed by the secondary sources that are installed
+    pub fn detect_all() -> Vec<Self> {
+        Self::detect_all_with(&SystemRunner, &OsRelease::load())
+    }
+
+    /// `detect_all` using `runner` to probe binaries
+    pub fn detect_all_with(runner: &impl CommandRunner, os_release: &OsRelease) -> Vec<Self> {
+        let mut backends: Vec<Self> = Self::detect_with(runner, os_release).into_iter().collect();
+
+        for pm in [Self::Flatpak, Self::Snap] {
+            if Self::binary_works(runner, pm.binary()) {
+                tracing::debug!("Detected secondary update source: {}", pm);
+                backends.push(pm);
+            }
+        }
+
+        backends
+    }
+
+    /// Native package manager of a distribution, by os-release `ID`
+    fn for_distro_id(id: &str) -> Option<Self> {
+        match id {
+            "debian" | "ubuntu" => Some(Self::Apt),
+            "fedora" | "rhel" | "centos" => Some(Self::Dnf),
+            "arch" => Some(Self::Pacman),
+            "suse" | "opensuse" => Some(Self::Zypper),
+            "alpine" => Some(Self::Apk),
+            "void" => Some(Self::Xbps),
+            "gentoo" => Some(Self::Emerge),
+            "solus" => Some(Self::Eopkg),
+            "nixos" => Some(Self::Nix),
+            _ => None,
+        }
+    }
+
+    /// Binary probed to detect this backend
+    fn binary(&self) -> &'static str {
+        match self {
+            Self::Apt => "apt",
+            Self::Dnf => "dnf",
+            Self::Pacman => "pacman",
+            Self::Zypper => "zypper",
+            Self::Apk => "apk",
+            Self::Xbps => "xbps-install",
+            Self::Emerge => "emerge",
+            Self::Eopkg => "eopkg",
+            Self::Nix => "nix-env",
+            Self::Flatpak => "flatpak",
+            Self::Snap => "snap",
+        }
+    }
+
+    /// Check that `cmd --version` runs and exits successfully
+    fn binary_works(runner: &impl CommandRunner, cmd: &str) -> bool {
+        runner
+            .run(&[cmd.to_string(), "--version".to_string()])
+            .map(|output| output.success())
+            .unwrap_or(false)
+    }
+
+    /// Whether this is the distribution's own package manager rather than a
+    /// secondary source such as Flatpak or Snap
+    #[allow(dead_code)]
+    pub fn is_native(&self) -> bool {
+        !matches!(self, Self::Flatpak | Self::Snap)
+    }
+
+    /// Whether upgrading needs root. Flatpak asks polkit itself when touching the
+    /// system installation and nix-env upgrades the user's own profile, so both
+    /// run as the user.
+    pub fn upgrade_requires_root(&self) -> bool {
+        !matches!(self, Self::Flatpak | Self::Nix)
+    }
+
+    /// Get the update check command
+    ///
+    /// The check runs unprivileged, so apt lists the updates known to the current
+    /// package cache. Refreshing it needs root and is left to `apt-get update` as
+    /// run by the `apt-daily.timer` or a software center; the upgrade refreshes
+    /// it itself.
+    #[allow(dead_code)]
+    pub fn update_check_command(&self) -> Vec<String> {
+        match self {
+            Self::Apt => vec!["apt", "list", "--upgradable"],
+            Self::Dnf => vec!["dnf", "check-update"],
+            Self::Pacman => vec!["checkupdates"],
+            Self::Zypper => vec!["zypper", "list-updates"],
+            Self::Apk => vec!["apk", "list", "--upgradable"],
+            Self::Xbps => vec!["xbps-install", "--memory-sync", "--dry-run", "--update"],
+            Self::Emerge => vec![
+                "emerge",
+                "--pretend",
+                "--update",
+                "--deep",
+                "--newuse",
+                "--color=n",
+                "--nospinner",
+                "@world",
+            ],
+            Self::Eopkg => vec!["eopkg", "list-upgrades", "--no-color"],
+            Self::Nix => vec!["nix-env", "--upgrade", "--dry-run"],
+            Self::Flatpak => vec![
+                "flatpak",
+                "remote-ls",
+                "--updates",
+                "--columns=application,version,branch,arch,origin",
+            ],
+            Self::Snap => vec!["snap", "refresh", "--list"],
+        }
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    /// Get the non-interactive upgrade command (without the pkexec prefix)
+    ///
+    /// apt refreshes the package cache with `apt-get update` first, in the same
+    /// privileged shell, so a stale cache does not hide new updates.
+    pub fn upgrade_command(&self) -> Vec<String> {
+        match self {
+            Self::Apt => vec![
+                "sh",
+                "-c",
+                "apt-get update && DEBIAN_FRONTEND=noninteractive apt-get -y \
+                 -o Dpkg::Options::=--force-confdef -o Dpkg::Options::=--force-confold \
+                 full-upgrade",
+            ],
+            Self::Dnf => vec!["dnf", "-y", "upgrade"],
+            Self::Pacman => vec!["pacman", "-Syu", "--noconfirm"],
+            Self::Zypper => vec!["zypper", "--non-interactive", "update"],
+            Self::Apk => vec!["apk", "upgrade"],
+            Self::Xbps => vec!["xbps-install", "--sync", "--update", "--yes"],
+            Self::Emerge => vec![
+                "emerge",
+                "--update",
+                "--deep",
+                "--newuse",
+                "--color=n",
+                "--nospinner",
+                "@world",
+            ],
+            Self::Eopkg => vec!["eopkg", "upgrade", "--yes-all"],
+            Self::Nix => vec!["nix-env", "--upgrade"],
+            Self::Flatpak => vec!["flatpak", "update", "-y", "--noninteractive"],
+            Self::Snap => vec!["snap", "refresh"],
+        }
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    /// Run the upgrade (through pkexec when it needs root), passing every
+    /// stdout/stderr line to `on_line` as it is printed. Blocks until the upgrade
+    /// finishes and returns the exit code (pkexec itself exits with 126/127 when
+    /// authorization is refused).
+    pub fn run_upgrade(&self, on_line: impl FnMut(String)) -> Result<i32> {
+        self.run_upgrade_with(&SystemRunner, on_line)
+    }
+
+    /// `run_upgrade` using `runner` to execute the upgrade command
+    pub fn run_upgrade_with(
+        &self,
+        runner: &impl CommandRunner,
+        mut on_line: impl FnMut(String),
+    ) -> Result<i32> {
+        let mut cmd = self.upgrade_command();
+        if self.upgrade_requires_root() {
+            cmd.insert(0, "pkexec".to_string());
+        }
+        tracing::info!("Running upgrade: {}", cmd.join(" "));
+
+        let code = runner.run_streaming(&cmd, &mut on_line).map_err(|e| {
+            tracing::error!("Failed to start upgrade {:?}: {}", cmd, e);
+            RoStartError::CommandFailed(format!("Failed to start {}: {}", cmd[0], e))
+        })?;
+
+        // A missing code means the process was killed by a signal
+        let code = code.unwrap_or(-1);
+        tracing::info!("{} upgrade finished with exit code {}", self, code);
+
+        Ok(code)
+    }
+
+    /// Check for available updates
+    #[allow(dead_code)]
+    pub fn check_updates(&self) -> Result<UpdateInfo> {
+        self.check_updates_with(&SystemRunner)
+    }
+
+    /// `check_updates` using `runner` to execute the update check command
+    pub fn check_updates_with(&self, runner: &impl CommandRunner) -> Result<UpdateInfo> {
+        Ok(UpdateInfo::from_sources(vec![self.fetch_updates(runner)?]))
+    }
+
+    /// Check every backend in `backends` and aggregate the results per source.
+    ///
+    /// A failing backend is logged and left out of the breakdown; an error is
+    /// returned only when no backend could be checked.
+    #[allow(dead_code)]
+    pub fn check_all_updates(backends: &[Self]) -> Result<UpdateInfo> {
+        Self::check_all_updates_with(&SystemRunner, backends)
+    }
+
+    /// `check_all_updates` using `runner` to execute the update check commands
+    pub fn check_all_updates_with(
+        runner: &impl CommandRunner,
+        backends: &[Self],
+    ) -> Result<UpdateInfo> {
+        UpdateInfo::aggregate(backends.iter().map(|pm| (pm, pm.fetch_updates(runner))))
+    }
+
+    /// Run the update check command for this backend
+    fn fetch_updates(&self, runner: &impl CommandRunner) -> Result<SourceUpdates> {
+        let cmd = self.update_check_command();
+
+        let output = runner.run(&cmd).map_err(|e| {
+            tracing::error!("Failed to execute {:?}: {}", cmd, e);
+            RoStartError::UpdateCheckFailed(format!("Command execution failed: {}", e))
+        })?;
+
+        let mut source = self.source_updates_from_output(&cmd, &output)?;
+        if let Some(cmd) = self.advisory_command(&source) {
+            self.classify_updates(&mut source.updates, runner.run(&cmd));
+        }
+        Ok(source)
+    }
+
+    /// Check for available updates without blocking the calling thread.
+    ///
+    /// Must run on the background runtime (see `crate::runtime::spawn`). The check
+    /// is aborted, and the child process killed, when `options.timeout` elapses or
+    /// `options.cancel` is triggered. `progress` is called as the check advances.
+    #[allow(dead_code)]
+    pub async fn check_updates_async(
+        &self,
+        options: UpdateCheckOptions,
+        progress: impl Fn(UpdateCheckProgress) + Send + Sync,
+    ) -> Result<UpdateInfo> {
+        let backends = std::slice::from_ref(self);
+        Self::check_all_updates_async_with(&SystemRunner, backends, options, progress).await
+    }
+
+    /// Asynchronous variant of `check_all_updates`. The timeout applies to each
+    /// backend separately; cancellation stops the whole check.
+    pub async fn check_all_updates_async(
+        backends: &[Self],
+        options: UpdateCheckOptions,
+        progress: impl Fn(UpdateCheckProgress) + Send + Sync,
+    ) -> Result<UpdateInfo> {
+        Self::check_all_updates_async_with(&SystemRunner, backends, options, progress).await
+    }
+
+    /// `check_all_updates_async` using `runner` to execute the update check commands
+    pub async fn check_all_updates_async_with(
+        runner: &impl CommandRunner,
+        backends: &[Self],
+        options: UpdateCheckOptions,
+        progress: impl Fn(UpdateCheckProgress) + Send + Sync,
+    ) -> Result<UpdateInfo> {
+        let mut results = Vec::with_capacity(backends.len());
+        for pm in backends {
+            match pm.fetch_updates_async(runner, &options, &progress).await {
+                Err(RoStartError::UpdateCheckCancelled) => {
+                    return Err(RoStartError::UpdateCheckCancelled)
+                }
+                result => results.push((pm, result)),
+            }
+        }
+
+        let info = UpdateInfo::aggregate(results);
+        progress(UpdateCheckProgress::Done);
+
+        info
+    }
+
+    /// Run the update check command for this backend on the Tokio runtime
+    async fn fetch_updates_async(
+        &self,
+        runner: &impl CommandRunner,
+        options: &UpdateCheckOptions,
+        progress: &impl Fn(UpdateCheckProgress),
+    ) -> Result<SourceUpdates> {
+        let cmd = self.update_check_command();
+
+        // Reported before awaiting the command so the stage shows while it runs
+        progress(if self.refreshes_metadata() {
+            UpdateCheckProgress::RefreshingMetadata
+        } else {
+            UpdateCheckProgress::Resolving
+        });
+
+        // Dropping the pending future kills the child
+        let output = tokio::select! {
+            result = tokio::time::timeout(options.timeout, runner.run_async(&cmd)) => {
+                result.map_err(|_| {
+                    tracing::warn!("Update check {:?} timed out after {:?}", cmd, options.timeout);
+                    RoStartError::UpdateCheckTimedOut(options.timeout.as_secs())
+                })?.map_err(|e| {
+                    tracing::error!("Failed to execute {:?}: {}", cmd, e);
+                    RoStartError::UpdateCheckFailed(format!("Command execution failed: {}", e))
+                })?
+            }
+            _ = options.cancel.cancelled() => {
+                tracing::info!("Update check {:?} cancelled", cmd);
+                return Err(RoStartError::UpdateCheckCancelled);
+            }
+        };
+
+        let mut source = self.source_updates_from_output(&cmd, &output)?;
+        if let Some(cmd) = self.advisory_command(&source) {
+            progress(UpdateCheckProgress::Resolving);
+            let output = tokio::select! {
+                result = tokio::time::timeout(options.timeout, runner.run_async(&cmd)) => {
+                    result.unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")))
+                }
+                _ = options.cancel.cancelled() => return Err(RoStartError::UpdateCheckCancelled),
+            };
+            self.classify_updates(&mut source.updates, output);
+        }
+        Ok(source)
+    }
+
+    /// Whether the update check command syncs repository metadata before
+    /// resolving the updates
+    fn refreshes_metadata(&self) -> bool {
+        matches!(self, Self::Dnf | Self::Zypper | Self::Pacman | Self::Xbps)
+    }
+
+    /// Exit codes of the update check command that do not indicate a failure
+    fn check_success_codes(&self) -> &'static [i32] {
+        match self {
+            // dnf check-update returns 100 when updates are available
+            Self::Dnf => &[0, 100],
+            // checkupdates returns 2 when there is nothing to update
+            Self::Pacman => &[0, 2],
+            _ => &[0],
+        }
+    }
+
+    /// Parse the raw output of the update check command, failing on unexpected
+    /// exit codes so that a broken check is not reported as "up to date"
+    fn source_updates_from_output(
+        &self,
+        cmd: &[String],
+        output: &CommandOutput,
+    ) -> Result<SourceUpdates> {
+        if !output
+            .code
+            .is_some_and(|code| self.check_success_codes().contains(&code))
+        {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+            tracing::warn!(
+                "Update check command {:?} returned {:?}: {}",
+                cmd,
+                output.code,
+                stderr.trim()
+            );
+            return Err(RoStartError::UpdateCheckFailed(
+                match (output.code, reason) {
+                    (Some(code), Some(reason)) => format!("exit code {}: {}", code, reason.trim()),
+                    (Some(code), None) => format!("exit code {}", code),
+                    (None, _) => "terminated by a signal".to_string(),
+                },
+            ));
+        }
+
+        // nix-env reports what it would upgrade on stderr
+        let raw = match self {
+            Self::Nix => &output.stderr,
+            _ => &output.stdout,
+        };
+        let updates = self.parse_updates(&String::from_utf8_lossy(raw));
+
+        tracing::debug!("Found {} updates using {:?}", updates.len(), self);
+
+        Ok(SourceUpdates {
+            package_manager: self.clone(),
+            updates,
+        })
+    }
+
+    /// Parse the output of `update_check_command` into a list of pending updates
+    pub fn parse_updates(&self, output: &str) -> Vec<PendingUpdate> {
+        match self {
+            Self::Apt => parse_apt_upgradable(output),
+            Self::Dnf => parse_dnf_check_update(output),
+            Self::Pacman => parse_checkupdates(output),
+            Self::Zypper => parse_zypper_list_updates(output),
+            Self::Apk => parse_apk_upgradable(output),
+            Self::Xbps => parse_xbps_dry_run(output),
+            Self::Emerge => parse_emerge_pretend(output),
+            Self::Eopkg => parse_eopkg_list_upgrades(output),
+            Self::Nix => parse_nix_env_dry_run(output),
+            Self::Flatpak => parse_flatpak_remote_ls(output),
+            Self::Snap => parse_snap_refresh_list(output),
+        }
+    }
+
+    /// Command listing the advisories behind the pending updates in `source`.
+    /// apt needs none: its security fixes come from the `-security` pocket.
+    fn advisory_command(&self, source: &SourceUpdates) -> Option<Vec<String>> {
+        if source.updates.is_empty() {
+            return None;
+        }
+
+        let cmd: &[&str] = match self {
+            Self::Dnf => &["dnf", "--quiet", "updateinfo", "list", "--updates"],
+            Self::Zypper => &["zypper", "--non-interactive", "list-patches"],
+            Self::Pacman => &["arch-audit", "--upgradable", "--format", "%n %s"],
+            _ => return None,
+        };
+        Some(cmd.iter().map(|s| s.to_string()).collect())
+    }
+
+    /// Mark `updates` with the category and severity of their advisories. The
+    /// advisory tools are optional (arch-audit is a separate package), so any
+    /// failure just leaves the updates unclassified.
+    fn classify_updates(&self, updates: &mut [PendingUpdate], output: io::Result<CommandOutput>) {
+        let output = match output {
+            // zypper list-patches returns 100/101 when (security) patches are needed
+            Ok(output) if matches!(output.code, Some(0 | 100 | 101)) => output,
+            Ok(output) => {
+                tracing::debug!("{} advisory check returned {:?}", self, output.code);
+                return;
+            }
+            Err(e) => {
+                tracing::debug!("{} advisories unavailable: {}", self, e);
+                return;
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let advisories = match self {
+            Self::Dnf => parse_dnf_updateinfo(&stdout),
+            Self::Zypper => parse_zypper_list_patches(&stdout),
+            Self::Pacman => parse_arch_audit(&stdout),
+            _ => Vec::new(),
+        };
+
+        for update in updates.iter_mut() {
+            for advisory in advisories.iter().filter(|a| a.package == update.name) {
+                update.category = update.category.clone().max(advisory.category.clone());
+                update.severity = update.severity.max(advisory.severity);
+            }
+        }
+    }
+}
+
+/// A single package that has a newer version available
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PendingUpdate {
+    pub name: String,
+    /// Installed version, when the backend reports it
+    pub current_version: Option<String>,
+    /// Candidate version; empty when the backend does not report it (eopkg)
+    pub new_version: String,
+    pub arch: Option<String>,
+    pub repository: Option<String>,
+    pub category: UpdateCategory,
+    /// Advisory severity, when the distribution publishes one
+    pub severity: Option<UpdateSeverity>,
+}
+
+impl PendingUpdate {
+    pub fn is_security(&self) -> bool {
+        self.category == UpdateCategory::Security
+    }
+}
+
+impl fmt::Display for PendingUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.current_version {
+            Some(current) => write!(f, "{} {} -> {}", self.name, current, self.new_version),
+            None if self.new_version.is_empty() => write!(f, "{}", self.name),
+            None => write!(f, "{} {}", self.name, self.new_version),
+        }
+    }
+}
+
+/// Kind of change an update brings, ordered from least to most important
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateCategory {
+    #[default]
+    Other,
+    Enhancement,
+    Bugfix,
+    Security,
+}
+
+impl UpdateCategory {
+    /// Map an advisory type as printed by dnf or zypper
+    fn from_label(label: &str) -> Self {
+        match label.to_lowercase().as_str() {
+            "security" => Self::Security,
+            "bugfix" | "recommended" => Self::Bugfix,
+            "enhancement" | "feature" | "optional" | "newpackage" => Self::Enhancement,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl fmt::Display for UpdateCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other => write!(f, "other"),
+            Self::Enhancement => write!(f, "enhancement"),
+            Self::Bugfix => write!(f, "bugfix"),
+            Self::Security => write!(f, "security"),
+        }
+    }
+}
+
+/// Advisory severity, normalised across distributions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateSeverity {
+    Low,
+    Moderate,
+    Important,
+    Critical,
+}
+
+impl UpdateSeverity {
+    /// Map the severity labels used by dnf, zypper and arch-audit
+    fn from_label(label: &str) -> Option<Self> {
+        match label.to_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "moderate" | "medium" => Some(Self::Moderate),
+            "important" | "high" => Some(Self::Important),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UpdateSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Moderate => write!(f, "moderate"),
+            Self::Important => write!(f, "important"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+/// Default time limit for a single update check
+pub const DEFAULT_UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Stages reported by `PackageManager::check_updates_async`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateCheckProgress {
+    RefreshingMetadata,
+    Resolving,
+    Done,
+}
+
+/// Cloneable handle used to cancel an in-flight asynchronous operation
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<CancelInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancelInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every operation holding a clone of this token
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolve once `cancel` has been called
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            // Register interest before checking the flag so a concurrent cancel is not missed
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Options for `PackageManager::check_updates_async`
+#[derive(Debug, Clone)]
+pub struct UpdateCheckOptions {
+    pub timeout: Duration,
+    pub cancel: CancelToken,
+}
+
+impl Default for UpdateCheckOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_UPDATE_CHECK_TIMEOUT,
+            cancel: CancelToken::new(),
+        }
+    }
+}
+
+/// Pending updates reported by a single backend
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceUpdates {
+    pub package_manager: PackageManager,
+    pub updates: Vec<PendingUpdate>,
+}
+
+/// Result of an update check. Serialized as the `updates` section of the
+/// versioned output schema (see `crate::output`).
+#[derive(Debug, Clone, Serialize)]
+#[allow(dead_code)]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub count: usize,
+    /// Updates that fix security issues, included in `count`
+    pub security_count: usize,
+    /// Breakdown by backend, in detection order (native package manager first)
+    pub sources: Vec<SourceUpdates>,
+}
+
+impl UpdateInfo {
+    pub(crate) fn from_sources(sources: Vec<SourceUpdates>) -> Self {
+        let count = sources.iter().map(|source| source.updates.len()).sum();
+        let security_count = sources
+            .iter()
+            .flat_map(|source| source.updates.iter())
+            .filter(|update| update.is_security())
+            .count();
+        Self {
+            available: count > 0,
+            count,
+            security_count,
+            sources,
+        }
+    }
+
+    /// Combine per-backend results, skipping failed backends unless all of them failed
+    fn aggregate<'a>(
+        results: impl IntoIterator<Item = (&'a PackageManager, Result<SourceUpdates>)>,
+    ) -> Result<Self> {
+        let mut sources = Vec::new();
+        let mut first_error = None;
+
+        for (pm, result) in results {
+            match result {
+                Ok(source) => sources.push(source),
+                Err(e) => {
+                    tracing::warn!("Skipping {} updates: {}", pm, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if sources.is_empty() => Err(e),
+            _ => Ok(Self::from_sources(sources)),
+        }
+    }
+
+    /// All pending updates across every source
+    #[allow(dead_code)]
+    pub fn updates(&self) -> impl Iterator<Item = &PendingUpdate> {
+        self.sources.iter().flat_map(|source| source.updates.iter())
+    }
+
+    /// Stable identifier of the pending update set, independent of the order in
+    /// which the backends listed them. Used to avoid repeating notifications.
+    /// It is saved in the config, so it uses FNV-1a, whose output does not
+    /// change between Rust releases like `DefaultHasher`'s may.
+    pub fn fingerprint(&self) -> String {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut entries: Vec<String> = self
+            .sources
+            .iter()
+            .flat_map(|source| {
+                source.updates.iter().map(move |update| {
+                    format!(
+                        "{}:{}:{}",
+                        source.package_manager, update.name, update.new_version
+                    )
+                })
+            })
+            .collect();
+        entries.sort();
+
+        let hash = entries
+            .join("\n")
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            });
+        format!("{:016x}", hash)
+    }
+
+    #[allow(dead_code)]
+    pub fn message(&self) -> String {
+        if !self.available {
+            return "System is up to date!".to_string();
+        }
+
+        let breakdown: Vec<String> = self
+            .sources
+            .iter()
+            .filter(|source| !source.updates.is_empty())
+            .map(|source| format!("{}: {}", source.package_manager, source.updates.len()))
+            .collect();
+
+        let security = match self.security_count {
+            0 => String::new(),
+            n => format!(", {} security", n),
+        };
+
+        format!(
+            "{} update(s) available{} ({})",
+            self.count,
+            security,
+            breakdown.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use runner::FakeRunner;
+
+    const APT_FIXTURE: &str = include_str!("fixtures/apt-list-upgradable.txt");
+    const FLATPAK_FIXTURE: &str = include_str!("fixtures/flatpak-remote-ls.txt");
+    const DNF_FIXTURE: &str = include_str!("fixtures/dnf-check-update.txt");
+
+    const DNF_CHECK: &[&str] = &["dnf", "check-update"];
+
+    #[test]
+    fn test_update_info_breakdown_by_source() {
+        let native = SourceUpdates {
+            package_manager: PackageManager::Apt,
+            updates: PackageManager::Apt.parse_updates(APT_FIXTURE),
+        };
+        let flatpak = SourceUpdates {
+            package_manager: PackageManager::Flatpak,
+            updates: PackageManager::Flatpak.parse_updates(FLATPAK_FIXTURE),
+        };
+        let snap = SourceUpdates {
+            package_manager: PackageManager::Snap,
+            updates: Vec::new(),
+        };
+
+        let info = UpdateInfo::from_sources(vec![native, flatpak, snap]);
+        assert!(info.available);
+        assert_eq!(info.count, 6);
+        assert_eq!(info.updates().count(), 6);
+        assert_eq!(info.security_count, 2);
+        assert_eq!(
+            info.message(),
+            "6 update(s) available, 2 security (apt: 3, flatpak: 3)"
+        );
+    }
+
+    #[test]
+    fn test_update_info_fingerprint() {
+        let source = |updates: Vec<PendingUpdate>| SourceUpdates {
+            package_manager: PackageManager::Apt,
+            updates,
+        };
+        let mut updates = PackageManager::Apt.parse_updates(APT_FIXTURE);
+        let info = UpdateInfo::from_sources(vec![source(updates.clone())]);
+
+        updates.reverse();
+        let reordered = UpdateInfo::from_sources(vec![source(updates.clone())]);
+        assert_eq!(info.fingerprint(), reordered.fingerprint());
+
+        updates[0].new_version.push_str("+1");
+        let changed = UpdateInfo::from_sources(vec![source(updates)]);
+        assert_ne!(info.fingerprint(), changed.fingerprint());
+
+        // Saved values must stay valid across builds
+        assert_eq!(
+            UpdateInfo::from_sources(Vec::new()).fingerprint(),
+            "cbf29ce484222325"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_skips_failed_sources() {
+        let apt = PackageManager::Apt;
+        let snap = PackageManager::Snap;
+        let ok = SourceUpdates {
+            package_manager: apt.clone(),
+            updates: apt.parse_updates(APT_FIXTURE),
+        };
+
+        let info = UpdateInfo::aggregate([
+            (&apt, Ok(ok)),
+            (&snap, Err(RoStartError::UpdateCheckFailed("snapd".into()))),
+        ])
+        .unwrap();
+        assert_eq!(info.sources.len(), 1);
+        assert_eq!(info.count, 3);
+
+        let all_failed =
+            UpdateInfo::aggregate([(&snap, Err(RoStartError::UpdateCheckFailed("snapd".into())))]);
+        assert!(all_failed.is_err());
+    }
+
+    #[test]
+    fn test_native_and_secondary_backends() {
+        assert!(PackageManager::Dnf.is_native());
+        assert!(!PackageManager::Flatpak.is_native());
+        assert!(!PackageManager::Snap.is_native());
+        assert!(!PackageManager::Flatpak.upgrade_requires_root());
+        assert!(PackageManager::Snap.upgrade_requires_root());
+        assert!(PackageManager::Emerge.upgrade_requires_root());
+        assert!(!PackageManager::Nix.upgrade_requires_root());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_token_wakes_waiters() {
+        let token = CancelToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+
+        assert!(!token.is_cancelled());
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("cancelled() should resolve after cancel()")
+            .unwrap();
+
+        // Already-cancelled tokens resolve immediately
+        token.cancelled().await;
+    }
+
+    #[test]
+    fn test_detect_with_skips_missing_binaries() {
+        // apt is missing entirely, dnf answers --version
+        let runner = FakeRunner::new().on(&["dnf", "--version"], 0, "4.18.2", "");
+
+        assert_eq!(
+            PackageManager::detect_with(&runner, &OsRelease::default()).unwrap(),
+            PackageManager::Dnf
+        );
+        assert_eq!(
+            runner.calls(),
+            [
+                vec!["apt".to_string(), "--version".to_string()],
+                vec!["dnf".to_string(), "--version".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_with_ignores_failing_binary() {
+        let runner = FakeRunner::new()
+            .on(&["apt", "--version"], 127, "", "apt: broken install")
+            .on(&["pacman", "--version"], 0, "Pacman v6.0.2", "");
+
+        assert_eq!(
+            PackageManager::detect_with(&runner, &OsRelease::default()).unwrap(),
+            PackageManager::Pacman
+        );
+    }
+
+    #[test]
+    fn test_detect_with_prefers_os_release() {
+        // An openSUSE derivative that also has apt installed
+        let runner = FakeRunner::new()
+            .on(&["apt", "--version"], 0, "apt 2.7.14", "")
+            .on(&["zypper", "--version"], 0, "zypper 1.14.68", "");
+        let os_release = OsRelease::parse("ID=\"gecko\"\nID_LIKE=\"opensuse suse\"\n");
+
+        assert_eq!(
+            PackageManager::detect_with(&runner, &os_release).unwrap(),
+            PackageManager::Zypper
+        );
+        assert_eq!(
+            runner.calls(),
+            [vec!["zypper".to_string(), "--version".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_detect_with_no_backend() {
+        let result = PackageManager::detect_with(&FakeRunner::new(), &OsRelease::default());
+        assert!(matches!(result, Err(RoStartError::PackageManagerNotFound)));
+        assert!(
+            PackageManager::detect_all_with(&FakeRunner::new(), &OsRelease::default()).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_detect_all_with_secondary_sources() {
+        let runner = FakeRunner::new()
+            .on(&["zypper", "--version"], 0, "zypper 1.14.68", "")
+            .on(&["flatpak", "--version"], 0, "Flatpak 1.14.4", "");
+
+        assert_eq!(
+            PackageManager::detect_all_with(&runner, &OsRelease::default()),
+            [PackageManager::Zypper, PackageManager::Flatpak]
+        );
+    }
+
+    #[test]
+    fn test_check_updates_with_dnf_exit_code_100() {
+        let runner = FakeRunner::new().on(DNF_CHECK, 100, DNF_FIXTURE, "");
+
+        let info = PackageManager::Dnf.check_updates_with(&runner).unwrap();
+        assert!(info.available);
+        assert_eq!(info.count, 3);
+        assert_eq!(info.sources[0].package_manager, PackageManager::Dnf);
+    }
+
+    #[test]
+    fn test_check_updates_with_dnf_security_advisories() {
+        let runner = FakeRunner::new().on(DNF_CHECK, 100, DNF_FIXTURE, "").on(
+            &["dnf", "--quiet", "updateinfo", "list", "--updates"],
+            0,
+            include_str!("fixtures/dnf-updateinfo-list.txt"),
+            "",
+        );
+
+        let info = PackageManager::Dnf.check_updates_with(&runner).unwrap();
+        assert_eq!(info.security_count, 1);
+        let firefox = info.updates().find(|u| u.name == "firefox").unwrap();
+        assert!(firefox.is_security());
+        assert_eq!(firefox.severity, Some(UpdateSeverity::Important));
+        let kernel = info.updates().find(|u| u.name == "kernel-core").unwrap();
+        assert_eq!(kernel.category, UpdateCategory::Bugfix);
+    }
+
+    #[test]
+    fn test_check_updates_without_arch_audit_is_unclassified() {
+        let runner = FakeRunner::new().on(
+            &["checkupdates"],
+            0,
+            include_str!("fixtures/checkupdates.txt"),
+            "",
+        );
+
+        let info = PackageManager::Pacman.check_updates_with(&runner).unwrap();
+        assert_eq!(info.count, 3);
+        assert_eq!(info.security_count, 0);
+        assert!(info.updates().all(|u| u.category == UpdateCategory::Other));
+    }
+
+    #[test]
+    fn test_check_updates_with_stderr_only_failure() {
+        let runner = FakeRunner::new().on(
+            DNF_CHECK,
+            1,
+            "",
+            "Error: Failed to download metadata for repo 'updates'\n",
+        );
+
+        match PackageManager::Dnf.check_updates_with(&runner) {
+            Err(RoStartError::UpdateCheckFailed(reason)) => {
+                assert!(reason.contains("exit code 1"), "{}", reason);
+                assert!(reason.contains("Failed to download metadata"), "{}", reason);
+            }
+            other => panic!("expected UpdateCheckFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_updates_with_checkupdates_nothing_to_do() {
+        // checkupdates exits with 2 when the system is up to date
+        let runner = FakeRunner::new().on(&["checkupdates"], 2, "", "");
+
+        let info = PackageManager::Pacman.check_updates_with(&runner).unwrap();
+        assert!(!info.available);
+    }
+
+    #[test]
+    fn test_check_updates_with_missing_binary() {
+        let result = PackageManager::Pacman.check_updates_with(&FakeRunner::new());
+        assert!(matches!(result, Err(RoStartError::UpdateCheckFailed(_))));
+    }
+
+    #[test]
+    fn test_check_all_updates_with_skips_failed_backend() {
+        let runner = FakeRunner::new().on(DNF_CHECK, 100, DNF_FIXTURE, "").on(
+            &["snap", "refresh", "--list"],
+            1,
+            "",
+            "error: cannot communicate with server",
+        );
+
+        let info = PackageManager::check_all_updates_with(
+            &runner,
+            &[PackageManager::Dnf, PackageManager::Snap],
+        )
+        .unwrap();
+        assert_eq!(info.sources.len(), 1);
+        assert_eq!(info.count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_async_check_reports_progress() {
+        let runner = FakeRunner::new().on(DNF_CHECK, 100, DNF_FIXTURE, "");
+        let seen = std::sync::Mutex::new(Vec::new());
+
+        let info = PackageManager::check_all_updates_async_with(
+            &runner,
+            &[PackageManager::Dnf],
+            UpdateCheckOptions::default(),
+            |progress| seen.lock().unwrap().push(progress),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(info.count, 3);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                UpdateCheckProgress::RefreshingMetadata,
+                UpdateCheckProgress::Resolving,
+                UpdateCheckProgress::Done
+            ]
+        );
+
+        // apt does not refresh its lists while checking
+        let runner = FakeRunner::new().on(&["apt", "list", "--upgradable"], 0, APT_FIXTURE, "");
+        seen.lock().unwrap().clear();
+        PackageManager::check_all_updates_async_with(
+            &runner,
+            &[PackageManager::Apt],
+            UpdateCheckOptions::default(),
+            |progress| seen.lock().unwrap().push(progress),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [UpdateCheckProgress::Resolving, UpdateCheckProgress::Done]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_check_times_out() {
+        let runner = FakeRunner::new()
+            .on(DNF_CHECK, 100, DNF_FIXTURE, "")
+            .delayed(DNF_CHECK, Duration::from_secs(60));
+        let options = UpdateCheckOptions {
+            timeout: Duration::from_millis(20),
+            ..Default::default()
+        };
+
+        let result = PackageManager::check_all_updates_async_with(
+            &runner,
+            &[PackageManager::Dnf],
+            options,
+            |_| {},
+        )
+        .await;
+        // The only backend timed out, so the aggregate fails with its error
+        assert!(matches!(result, Err(RoStartError::UpdateCheckTimedOut(0))));
+    }
+
+    #[tokio::test]
+    async fn test_async_check_cancelled() {
+        let runner = FakeRunner::new()
+            .on(DNF_CHECK, 100, DNF_FIXTURE, "")
+            .delayed(DNF_CHECK, Duration::from_secs(60));
+        let options = UpdateCheckOptions::default();
+        options.cancel.cancel();
+
+        let result = PackageManager::check_all_updates_async_with(
+            &runner,
+            &[PackageManager::Dnf],
+            options,
+            |_| {},
+        )
+        .await;
+        assert!(matches!(result, Err(RoStartError::UpdateCheckCancelled)));
+    }
+
+    #[test]
+    fn test_run_upgrade_with_streams_output_through_pkexec() {
+        let runner = FakeRunner::new().on(
+            &["pkexec", "dnf", "-y", "upgrade"],
+            0,
+            "Upgrading: firefox\nComplete!\n",
+            "warning: cache is stale\n",
+        );
+        let mut lines = Vec::new();
+
+        let code = PackageManager::Dnf
+            .run_upgrade_with(&runner, |line| lines.push(line))
+            .unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(
+            lines,
+            ["Upgrading: firefox", "Complete!", "warning: cache is stale"]
+        );
+    }
+
+    #[test]
+    fn test_apt_upgrade_refreshes_the_cache() {
+        let cmd = PackageManager::Apt.upgrade_command();
+        assert_eq!(cmd[..2], ["sh", "-c"]);
+        let script = &cmd[2];
+        let update = script.find("apt-get update &&").unwrap();
+        assert!(update < script.find("full-upgrade").unwrap());
+        assert!(script.contains("DEBIAN_FRONTEND=noninteractive"));
+    }
+
+    #[test]
+    fn test_run_upgrade_with_reports_exit_code() {
+        // Flatpak runs without pkexec; 126 is what pkexec returns on a refused prompt
+        let runner = FakeRunner::new()
+            .on(&["flatpak", "update", "-y", "--noninteractive"], 1, "", "")
+            .on(&["pkexec", "pacman", "-Syu", "--noconfirm"], 126, "", "");
+
+        let noop = |_| {};
+        assert_eq!(
+            PackageManager::Flatpak
+                .run_upgrade_with(&runner, noop)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            PackageManager::Pacman
+                .run_upgrade_with(&runner, noop)
+                .unwrap(),
+            126
+        );
+        assert!(matches!(
+            PackageManager::Zypper.run_upgrade_with(&runner, noop),
+            Err(RoStartError::CommandFailed(_))
+        ));
+    }
+}
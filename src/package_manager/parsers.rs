@@ -0,0 +1,816 @@
+use super::{PendingUpdate, UpdateCategory, UpdateSeverity};
+
+/// Parse `apt list --upgradable`
+///
+/// `firefox/jammy-updates,jammy-security 121.0 amd64 [upgradable from: 120.0]`
+pub(super) fn parse_apt_upgradable(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (name, repository) = fields.next()?.split_once('/')?;
+            let new_version = fields.next()?;
+            let arch = fields.next()?;
+
+            // The bracketed suffix ends with the installed version
+            let current_version = line
+                .rsplit_once(']')
+                .and_then(|(before, _)| before.rsplit_once('['))
+                .and_then(|(_, inside)| inside.split_whitespace().last())
+                .map(str::to_string);
+
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version,
+                new_version: new_version.to_string(),
+                arch: Some(arch.to_string()),
+                repository: Some(repository.to_string()),
+                // Fixes published to the `-security` pocket, e.g. `jammy-security`
+                category: if repository.split(',').any(|r| r.ends_with("-security")) {
+                    UpdateCategory::Security
+                } else {
+                    UpdateCategory::Other
+                },
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `dnf check-update`
+///
+/// `firefox.x86_64    121.0-1.fc39    updates`
+///
+/// Long package names are wrapped by dnf onto an indented continuation line.
+pub(super) fn parse_dnf_check_update(output: &str) -> Vec<PendingUpdate> {
+    let mut updates = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            pending.clear();
+            continue;
+        }
+
+        // Obsoletes are listed after the updates and are not updates themselves
+        if line.to_lowercase().starts_with("obsoleting") {
+            break;
+        }
+
+        if !line.starts_with(char::is_whitespace) {
+            pending.clear();
+        }
+        pending.extend(line.split_whitespace());
+
+        if pending.len() < 3 {
+            continue;
+        }
+
+        if let [name_arch, version, repository] = pending[..] {
+            if let Some((name, arch)) = name_arch.rsplit_once('.') {
+                updates.push(PendingUpdate {
+                    name: name.to_string(),
+                    current_version: None,
+                    new_version: version.to_string(),
+                    arch: Some(arch.to_string()),
+                    repository: Some(repository.to_string()),
+                    category: UpdateCategory::Other,
+                    severity: None,
+                });
+            }
+        }
+        pending.clear();
+    }
+
+    updates
+}
+
+/// Parse `checkupdates` (pacman-contrib)
+///
+/// `firefox 120.0-1 -> 121.0-1`
+pub(super) fn parse_checkupdates(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(
+            |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [name, current, "->", new] => Some(PendingUpdate {
+                    name: name.to_string(),
+                    current_version: Some(current.to_string()),
+                    new_version: new.to_string(),
+                    arch: None,
+                    repository: None,
+                    category: UpdateCategory::Other,
+                    severity: None,
+                }),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// Parse `zypper list-updates`
+///
+/// The output is a `|`-separated table; columns are located by their header names.
+pub(super) fn parse_zypper_list_updates(output: &str) -> Vec<PendingUpdate> {
+    let mut lines = output.lines();
+
+    let header: Vec<&str> = match lines.find(|line| {
+        line.split('|')
+            .any(|column| column.trim() == "Name" || column.trim() == "Available Version")
+    }) {
+        Some(line) => line.split('|').map(str::trim).collect(),
+        None => return Vec::new(),
+    };
+    let column = |title: &str| header.iter().position(|c| *c == title);
+
+    let (Some(name_col), Some(new_col)) = (column("Name"), column("Available Version")) else {
+        return Vec::new();
+    };
+    let current_col = column("Current Version");
+    let arch_col = column("Arch");
+    let repo_col = column("Repository");
+
+    lines
+        .filter(|line| line.contains('|') && !line.starts_with("--"))
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let cell = |index: Option<usize>| {
+                index
+                    .and_then(|i| cells.get(i))
+                    .filter(|value| !value.is_empty())
+                    .map(|value| value.to_string())
+            };
+
+            Some(PendingUpdate {
+                name: cell(Some(name_col))?,
+                current_version: cell(current_col),
+                new_version: cell(Some(new_col))?,
+                arch: cell(arch_col),
+                repository: cell(repo_col),
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `apk list --upgradable`
+///
+/// `busybox-1.36.1-r16 x86_64 {busybox} (GPL-2.0-only) [upgradable from: busybox-1.36.1-r15]`
+pub(super) fn parse_apk_upgradable(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (name, new_version) = split_apk_pkgver(fields.next()?)?;
+            let arch = fields.next()?;
+
+            let current_version = line
+                .rsplit_once(']')
+                .and_then(|(before, _)| before.rsplit_once('['))
+                .and_then(|(_, inside)| inside.split_whitespace().last())
+                .and_then(split_apk_pkgver)
+                .map(|(_, version)| version.to_string());
+
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version,
+                new_version: new_version.to_string(),
+                arch: Some(arch.to_string()),
+                repository: None,
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Split an apk `name-version-rN` string into name and `version-rN`
+fn split_apk_pkgver(pkgver: &str) -> Option<(&str, &str)> {
+    let (rest, release) = pkgver.rsplit_once('-')?;
+    if !release.starts_with('r') {
+        return None;
+    }
+    let (name, _) = rest.rsplit_once('-')?;
+    Some((name, &pkgver[name.len() + 1..]))
+}
+
+/// Parse `xbps-install --memory-sync --dry-run --update`
+///
+/// `firefox-121.0_1 update x86_64 https://repo-default.voidlinux.org/current 239620096 67387736`
+///
+/// New dependencies are listed with the `install` action and are skipped.
+pub(super) fn parse_xbps_dry_run(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (name, new_version) = fields.next()?.rsplit_once('-')?;
+            if fields.next()? != "update" {
+                return None;
+            }
+
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version: None,
+                new_version: new_version.to_string(),
+                arch: fields.next().map(str::to_string),
+                repository: fields.next().map(str::to_string),
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `emerge --pretend --update --deep --newuse @world`
+///
+/// `[ebuild     U  ] www-client/firefox-121.0:rapid::gentoo [120.0.1:rapid::gentoo] USE="..."`
+///
+/// Only entries flagged `U` (upgrade) are reported; new and rebuilt packages are skipped.
+pub(super) fn parse_emerge_pretend(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (flags, rest) = line.strip_prefix("[ebuild")?.split_once(']')?;
+            if !flags.contains('U') {
+                return None;
+            }
+
+            let mut fields = rest.split_whitespace();
+            let package = fields.next()?;
+            let (atom, repository) = match package.split_once("::") {
+                Some((atom, repository)) => (atom, Some(repository.to_string())),
+                None => (package, None),
+            };
+            // Drop the slot/sub-slot
+            let atom = atom.split(':').next()?;
+            let (name, new_version) = split_gentoo_atom(atom)?;
+
+            let current_version = fields
+                .next()
+                .filter(|field| field.starts_with('['))
+                .map(|field| field.trim_matches(|c| c == '[' || c == ']'))
+                .and_then(|field| field.split(':').next())
+                .map(str::to_string);
+
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version,
+                new_version: new_version.to_string(),
+                arch: None,
+                repository,
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Split a `category/name-version` atom at the first hyphen that starts a valid
+/// Gentoo version, so names such as `font-adobe-100dpi` stay intact
+fn split_gentoo_atom(atom: &str) -> Option<(&str, &str)> {
+    atom.match_indices('-')
+        .map(|(index, _)| (&atom[..index], &atom[index + 1..]))
+        .find(|(_, version)| is_gentoo_version(version))
+}
+
+/// Check a string against the PMS version syntax:
+/// `1.2.3[a-z]` followed by `_alpha|_beta|_pre|_rc|_p` suffixes and `-rN`
+fn is_gentoo_version(version: &str) -> bool {
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    let version = match version.rsplit_once("-r") {
+        Some((base, revision)) if is_number(revision) => base,
+        _ => version,
+    };
+
+    let mut parts = version.split('_');
+    let Some(numeric) = parts.next() else {
+        return false;
+    };
+    let numeric = numeric
+        .strip_suffix(|c: char| c.is_ascii_lowercase())
+        .unwrap_or(numeric);
+    if !numeric.split('.').all(is_number) {
+        return false;
+    }
+
+    parts.all(|suffix| {
+        ["alpha", "beta", "pre", "rc", "p"].iter().any(|kind| {
+            suffix
+                .strip_prefix(kind)
+                .is_some_and(|n| n.is_empty() || is_number(n))
+        })
+    })
+}
+
+/// Parse `eopkg list-upgrades`
+///
+/// `        firefox - Mozilla Firefox web browser`
+///
+/// eopkg prints only the name and summary, so no versions are available.
+pub(super) fn parse_eopkg_list_upgrades(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, _summary) = line.split_once(" - ")?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version: None,
+                new_version: String::new(),
+                arch: None,
+                repository: None,
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `nix-env --upgrade --dry-run` (printed on stderr)
+///
+/// `upgrading 'firefox-120.0' to 'firefox-121.0'`
+pub(super) fn parse_nix_env_dry_run(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("upgrading '")?;
+            let (current, rest) = rest.split_once("' to '")?;
+            let new = rest.strip_suffix('\'')?;
+
+            let (name, new_version) = split_nix_drv_name(new);
+            let current_version = Some(split_nix_drv_name(current).1)
+                .filter(|version| !version.is_empty())
+                .map(str::to_string);
+
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version,
+                new_version: new_version.to_string(),
+                arch: None,
+                repository: None,
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Split a derivation name like `builtins.parseDrvName`: the version starts at the
+/// first hyphen that is not followed by a letter
+fn split_nix_drv_name(drv_name: &str) -> (&str, &str) {
+    drv_name
+        .match_indices('-')
+        .find(|(index, _)| {
+            drv_name[index + 1..]
+                .chars()
+                .next()
+                .is_some_and(|c| !c.is_alphabetic())
+        })
+        .map(|(index, _)| (&drv_name[..index], &drv_name[index + 1..]))
+        .unwrap_or((drv_name, ""))
+}
+
+/// Parse `flatpak remote-ls --updates --columns=application,version,branch,arch,origin`
+///
+/// Rows are tab-separated; many runtimes have no version, so the branch is used instead.
+pub(super) fn parse_flatpak_remote_ls(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [application, version, branch, arch, origin] = cells[..] else {
+                return None;
+            };
+            // Header row, printed when stdout is a terminal
+            if application.is_empty() || application == "Application ID" {
+                return None;
+            }
+
+            let new_version = if version.is_empty() { branch } else { version };
+            Some(PendingUpdate {
+                name: application.to_string(),
+                current_version: None,
+                new_version: new_version.to_string(),
+                arch: Some(arch.to_string()).filter(|a| !a.is_empty()),
+                repository: Some(origin.to_string()).filter(|o| !o.is_empty()),
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `snap refresh --list`
+///
+/// `firefox  121.0-1  3600  251MB  mozilla**  -`
+pub(super) fn parse_snap_refresh_list(output: &str) -> Vec<PendingUpdate> {
+    let mut lines = output.lines();
+
+    // "All snaps up to date." has no table at all
+    if !lines.any(|line| line.starts_with("Name") && line.contains("Version")) {
+        return Vec::new();
+    }
+
+    lines
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let version = fields.next()?;
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version: None,
+                new_version: version.to_string(),
+                arch: None,
+                repository: None,
+                category: UpdateCategory::Other,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+/// Classification of one package taken from the distribution's advisory data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Advisory {
+    pub package: String,
+    pub category: UpdateCategory,
+    pub severity: Option<UpdateSeverity>,
+}
+
+/// Parse `dnf updateinfo list --updates`
+///
+/// `FEDORA-2024-1a2b3c4d5e Important/Sec. firefox-121.0-1.fc39.x86_64`
+pub(super) fn parse_dnf_updateinfo(output: &str) -> Vec<Advisory> {
+    output
+        .lines()
+        .filter_map(
+            |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [_id, kind, nevra] => {
+                    // name-version-release.arch
+                    let (nvr, _arch) = nevra.rsplit_once('.')?;
+                    let mut parts = nvr.rsplitn(3, '-');
+                    let name = parts.nth(2)?;
+
+                    let (category, severity) = match kind.split_once('/') {
+                        Some((severity, "Sec.")) => (
+                            UpdateCategory::Security,
+                            UpdateSeverity::from_label(severity),
+                        ),
+                        _ => (UpdateCategory::from_label(kind), None),
+                    };
+                    Some(Advisory {
+                        package: name.to_string(),
+                        category,
+                        severity,
+                    })
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// Parse `zypper list-patches`
+///
+/// Patches are not listed per package, so the affected packages are taken from
+/// the summary: `Security update for curl` or `Recommended update for a, b and c`.
+pub(super) fn parse_zypper_list_patches(output: &str) -> Vec<Advisory> {
+    let mut lines = output.lines();
+
+    let header: Vec<&str> =
+        match lines.find(|line| line.split('|').any(|column| column.trim() == "Category")) {
+            Some(line) => line.split('|').map(str::trim).collect(),
+            None => return Vec::new(),
+        };
+    let column = |title: &str| header.iter().position(|c| *c == title);
+
+    let (Some(category_col), Some(summary_col)) = (column("Category"), column("Summary")) else {
+        return Vec::new();
+    };
+    let severity_col = column("Severity");
+
+    lines
+        .filter(|line| line.contains('|') && !line.starts_with("--"))
+        .flat_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let category = UpdateCategory::from_label(cells.get(category_col).unwrap_or(&""));
+            let severity = severity_col
+                .and_then(|col| cells.get(col))
+                .and_then(|label| UpdateSeverity::from_label(label));
+            let targets = cells
+                .get(summary_col)
+                .and_then(|summary| summary.split_once(" for "))
+                .map(|(_, targets)| targets.to_string())
+                .unwrap_or_default();
+
+            targets
+                .split(", ")
+                .flat_map(|target| target.split(" and "))
+                .map(str::trim)
+                .filter(|package| !package.is_empty() && !package.contains(' '))
+                .map(|package| Advisory {
+                    package: package.to_string(),
+                    category: category.clone(),
+                    severity,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Parse `arch-audit --upgradable --format "%n %s"`
+///
+/// `curl High`
+pub(super) fn parse_arch_audit(output: &str) -> Vec<Advisory> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (package, severity) = line.trim().split_once(' ')?;
+            Some(Advisory {
+                package: package.to_string(),
+                category: UpdateCategory::Security,
+                severity: UpdateSeverity::from_label(severity),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::PackageManager;
+
+    const APT_FIXTURE: &str = include_str!("fixtures/apt-list-upgradable.txt");
+    const DNF_FIXTURE: &str = include_str!("fixtures/dnf-check-update.txt");
+    const CHECKUPDATES_FIXTURE: &str = include_str!("fixtures/checkupdates.txt");
+    const ZYPPER_FIXTURE: &str = include_str!("fixtures/zypper-list-updates.txt");
+    const FLATPAK_FIXTURE: &str = include_str!("fixtures/flatpak-remote-ls.txt");
+    const SNAP_FIXTURE: &str = include_str!("fixtures/snap-refresh-list.txt");
+    const APK_FIXTURE: &str = include_str!("fixtures/apk-list-upgradable.txt");
+    const XBPS_FIXTURE: &str = include_str!("fixtures/xbps-install-dry-run.txt");
+    const EMERGE_FIXTURE: &str = include_str!("fixtures/emerge-pretend.txt");
+    const EOPKG_FIXTURE: &str = include_str!("fixtures/eopkg-list-upgrades.txt");
+    const NIX_FIXTURE: &str = include_str!("fixtures/nix-env-dry-run.txt");
+    const DNF_UPDATEINFO_FIXTURE: &str = include_str!("fixtures/dnf-updateinfo-list.txt");
+    const ZYPPER_PATCHES_FIXTURE: &str = include_str!("fixtures/zypper-list-patches.txt");
+    const ARCH_AUDIT_FIXTURE: &str = include_str!("fixtures/arch-audit.txt");
+
+    #[test]
+    fn test_parse_apt_upgradable() {
+        let updates = PackageManager::Apt.parse_updates(APT_FIXTURE);
+        assert_eq!(updates.len(), 3);
+        assert_eq!(
+            updates[0],
+            PendingUpdate {
+                name: "firefox".to_string(),
+                current_version: Some("120.0+build2-0ubuntu0.22.04.1".to_string()),
+                new_version: "121.0+build1-0ubuntu0.22.04.1".to_string(),
+                arch: Some("amd64".to_string()),
+                repository: Some("jammy-updates,jammy-security".to_string()),
+                category: UpdateCategory::Security,
+                severity: None,
+            }
+        );
+        assert_eq!(updates[2].arch.as_deref(), Some("all"));
+    }
+
+    #[test]
+    fn test_parse_dnf_check_update() {
+        let updates = PackageManager::Dnf.parse_updates(DNF_FIXTURE);
+        let names: Vec<&str> = updates.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "firefox",
+                "kernel-core",
+                "python3-some-really-long-package-name"
+            ]
+        );
+        assert_eq!(updates[1].new_version, "6.6.11-200.fc39");
+        assert_eq!(updates[1].arch.as_deref(), Some("x86_64"));
+        assert_eq!(updates[2].arch.as_deref(), Some("noarch"));
+        assert_eq!(updates[2].repository.as_deref(), Some("updates-testing"));
+        assert!(updates.iter().all(|u| u.current_version.is_none()));
+    }
+
+    #[test]
+    fn test_parse_checkupdates() {
+        let updates = PackageManager::Pacman.parse_updates(CHECKUPDATES_FIXTURE);
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[1].name, "linux");
+        assert_eq!(updates[1].current_version.as_deref(), Some("6.6.9.arch1-1"));
+        assert_eq!(updates[1].new_version, "6.6.10.arch1-1");
+        assert_eq!(updates[2].new_version, "1:23.3.3-1");
+    }
+
+    #[test]
+    fn test_parse_zypper_list_updates() {
+        let updates = PackageManager::Zypper.parse_updates(ZYPPER_FIXTURE);
+        assert_eq!(
+            updates[0],
+            PendingUpdate {
+                name: "MozillaFirefox".to_string(),
+                current_version: Some("120.0-1.1".to_string()),
+                new_version: "121.0-1.1".to_string(),
+                arch: Some("x86_64".to_string()),
+                repository: Some("Main Update Repository".to_string()),
+                category: UpdateCategory::Other,
+                severity: None,
+            }
+        );
+        assert_eq!(updates.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_apk_upgradable() {
+        let updates = PackageManager::Apk.parse_updates(APK_FIXTURE);
+        assert_eq!(
+            updates[0],
+            PendingUpdate {
+                name: "busybox".to_string(),
+                current_version: Some("1.36.1-r15".to_string()),
+                new_version: "1.36.1-r16".to_string(),
+                arch: Some("x86_64".to_string()),
+                repository: None,
+                category: UpdateCategory::Other,
+                severity: None,
+            }
+        );
+        assert_eq!(updates[1].name, "py3-setuptools");
+        assert_eq!(updates[1].current_version.as_deref(), Some("68.2.2-r0"));
+        assert_eq!(updates[2].new_version, "1.2.4_git20230717-r5");
+    }
+
+    #[test]
+    fn test_parse_xbps_dry_run() {
+        let updates = PackageManager::Xbps.parse_updates(XBPS_FIXTURE);
+        let names: Vec<&str> = updates.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, ["firefox", "linux6.6"]);
+        assert_eq!(updates[1].new_version, "6.6.10_1");
+        assert_eq!(
+            updates[0].repository.as_deref(),
+            Some("https://repo-default.voidlinux.org/current")
+        );
+    }
+
+    #[test]
+    fn test_parse_emerge_pretend() {
+        let updates = PackageManager::Emerge.parse_updates(EMERGE_FIXTURE);
+        let names: Vec<&str> = updates.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "dev-libs/openssl",
+                "www-client/firefox",
+                "media-fonts/font-adobe-100dpi",
+                "sys-devel/gcc"
+            ]
+        );
+        assert_eq!(updates[0].new_version, "3.0.12");
+        assert_eq!(updates[0].current_version.as_deref(), Some("3.0.11"));
+        assert_eq!(updates[0].repository.as_deref(), Some("gentoo"));
+        assert_eq!(updates[2].new_version, "1.0.4");
+        assert_eq!(updates[3].new_version, "13.2.1_p20240113-r1");
+        assert_eq!(
+            updates[3].current_version.as_deref(),
+            Some("13.2.1_p20231216")
+        );
+    }
+
+    #[test]
+    fn test_gentoo_version_syntax() {
+        assert!(is_gentoo_version("1.0"));
+        assert!(is_gentoo_version("1.0.2a_rc3-r12"));
+        assert!(is_gentoo_version("2024_p1"));
+        assert!(!is_gentoo_version("100dpi-1.0.4"));
+        assert!(!is_gentoo_version("r1"));
+        assert!(!is_gentoo_version("1.0_foo"));
+    }
+
+    #[test]
+    fn test_parse_eopkg_list_upgrades() {
+        let updates = PackageManager::Eopkg.parse_updates(EOPKG_FIXTURE);
+        let names: Vec<&str> = updates.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, ["firefox", "linux-current"]);
+        assert!(updates[0].new_version.is_empty());
+        assert_eq!(updates[0].to_string(), "firefox");
+        assert!(PackageManager::Eopkg
+            .parse_updates("No packages to upgrade.\n")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_nix_env_dry_run() {
+        let updates = PackageManager::Nix.parse_updates(NIX_FIXTURE);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].name, "firefox");
+        assert_eq!(updates[0].current_version.as_deref(), Some("120.0"));
+        assert_eq!(updates[0].new_version, "121.0");
+        assert_eq!(updates[1].name, "python3.11-requests");
+        assert_eq!(updates[1].new_version, "2.32.0");
+    }
+
+    #[test]
+    fn test_parse_flatpak_remote_ls() {
+        let updates = PackageManager::Flatpak.parse_updates(FLATPAK_FIXTURE);
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[0].name, "org.mozilla.firefox");
+        assert_eq!(updates[0].new_version, "121.0");
+        assert_eq!(updates[0].repository.as_deref(), Some("flathub"));
+        // Runtimes without a version fall back to their branch
+        assert_eq!(updates[1].new_version, "23.08");
+        assert_eq!(updates[2].repository.as_deref(), Some("gnome-nightly"));
+
+        let with_header = format!(
+            "Application ID\tVersion\tBranch\tArch\tOrigin\n{}",
+            FLATPAK_FIXTURE
+        );
+        assert_eq!(PackageManager::Flatpak.parse_updates(&with_header).len(), 3);
+    }
+
+    #[test]
+    fn test_parse_snap_refresh_list() {
+        let updates = PackageManager::Snap.parse_updates(SNAP_FIXTURE);
+        let names: Vec<&str> = updates.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, ["firefox", "snapd"]);
+        assert_eq!(updates[0].new_version, "121.0-1");
+        assert!(PackageManager::Snap
+            .parse_updates("All snaps up to date.\n")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_empty_output() {
+        for pm in [
+            PackageManager::Apt,
+            PackageManager::Dnf,
+            PackageManager::Pacman,
+            PackageManager::Zypper,
+            PackageManager::Apk,
+            PackageManager::Xbps,
+            PackageManager::Emerge,
+            PackageManager::Eopkg,
+            PackageManager::Nix,
+            PackageManager::Flatpak,
+            PackageManager::Snap,
+        ] {
+            assert!(pm.parse_updates("").is_empty(), "{} should parse empty", pm);
+        }
+        assert!(PackageManager::Apt
+            .parse_updates("Listing... Done\n")
+            .is_empty());
+        assert!(PackageManager::Zypper
+            .parse_updates("Loading repository data...\nNo updates found.\n")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_dnf_updateinfo() {
+        let advisories = parse_dnf_updateinfo(DNF_UPDATEINFO_FIXTURE);
+        assert_eq!(
+            advisories[0],
+            Advisory {
+                package: "firefox".to_string(),
+                category: UpdateCategory::Security,
+                severity: Some(UpdateSeverity::Important),
+            }
+        );
+        assert_eq!(advisories[1].package, "kernel-core");
+        assert_eq!(advisories[1].category, UpdateCategory::Bugfix);
+        assert_eq!(
+            advisories[2].package,
+            "python3-some-really-long-package-name"
+        );
+        assert_eq!(advisories[2].category, UpdateCategory::Enhancement);
+    }
+
+    #[test]
+    fn test_parse_zypper_list_patches() {
+        let advisories = parse_zypper_list_patches(ZYPPER_PATCHES_FIXTURE);
+        let packages: Vec<&str> = advisories.iter().map(|a| a.package.as_str()).collect();
+        assert_eq!(packages, ["MozillaFirefox", "timezone", "timezone-java"]);
+        assert_eq!(advisories[0].category, UpdateCategory::Security);
+        assert_eq!(advisories[0].severity, Some(UpdateSeverity::Important));
+        assert_eq!(advisories[1].category, UpdateCategory::Bugfix);
+        assert_eq!(advisories[1].severity, Some(UpdateSeverity::Moderate));
+    }
+
+    #[test]
+    fn test_parse_arch_audit() {
+        let advisories = parse_arch_audit(ARCH_AUDIT_FIXTURE);
+        assert_eq!(advisories.len(), 2);
+        assert_eq!(advisories[0].package, "firefox");
+        assert_eq!(advisories[0].severity, Some(UpdateSeverity::Critical));
+        assert_eq!(advisories[1].severity, Some(UpdateSeverity::Important));
+        assert!(advisories
+            .iter()
+            .all(|a| a.category == UpdateCategory::Security));
+    }
+}
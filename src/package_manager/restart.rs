@@ -0,0 +1,317 @@
+use super::runner::{CommandRunner, SystemRunner};
+use super::PackageManager;
+use std::path::Path;
+
+/// Units the desktop session depends on. Restarting them from inside the
+/// session logs the user out or breaks it, so they call for a reboot instead.
+const SESSION_CRITICAL_UNITS: &[&str] = &[
+    "dbus.service",
+    "dbus-broker.service",
+    "systemd-logind.service",
+    "display-manager.service",
+    "gdm.service",
+    "gdm3.service",
+    "sddm.service",
+    "lightdm.service",
+    "lxdm.service",
+    "xdm.service",
+    "polkit.service",
+    "NetworkManager.service",
+];
+
+/// Pending reboot and outdated services left behind by an upgrade
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestartStatus {
+    pub reboot_required: bool,
+    /// Packages that asked for the reboot, when the distribution records them
+    pub reboot_packages: Vec<String>,
+    /// systemd services still running code from replaced libraries that can be
+    /// restarted safely; session-critical ones set `reboot_required` instead
+    pub services: Vec<String>,
+    /// `services` may be incomplete because the `/proc` fallback could not
+    /// inspect every service, usually for lack of root
+    pub services_unknown: bool,
+}
+
+impl RestartStatus {
+    /// Whether anything should be shown to the user
+    pub fn needs_attention(&self) -> bool {
+        self.reboot_required || !self.services.is_empty()
+    }
+
+    /// Fill `services` from the `/proc` scan
+    fn scan_proc(&mut self, root: &Path) {
+        let outdated = crate::system::services_using_deleted_libraries(root);
+        self.services = outdated.services;
+        self.services_unknown = !outdated.complete;
+    }
+}
+
+impl PackageManager {
+    /// Detect a pending reboot and services that need restarting after an upgrade
+    pub fn restart_status(&self) -> RestartStatus {
+        let kernel_release = sysinfo::System::kernel_version().unwrap_or_default();
+        self.restart_status_with(&SystemRunner, Path::new("/"), &kernel_release)
+    }
+
+    /// `restart_status` against an arbitrary filesystem `root`, running commands
+    /// through `runner` and treating `kernel_release` as the running kernel
+    pub fn restart_status_with(
+        &self,
+        runner: &impl CommandRunner,
+        root: &Path,
+        kernel_release: &str,
+    ) -> RestartStatus {
+        let run = |args: &[&str]| {
+            let cmd: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            runner.run(&cmd).ok()
+        };
+
+        let mut status = RestartStatus::default();
+
+        match self {
+            Self::Apt => {
+                status.reboot_required = ["run/reboot-required", "var/run/reboot-required"]
+                    .iter()
+                    .any(|file| root.join(file).exists());
+                status.reboot_packages =
+                    ["run/reboot-required.pkgs", "var/run/reboot-required.pkgs"]
+                        .iter()
+                        .find_map(|file| std::fs::read_to_string(root.join(file)).ok())
+                        .map(|pkgs| non_empty_lines(&pkgs))
+                        .unwrap_or_default();
+
+                // needrestart is optional; fall back to scanning /proc without it
+                match run(&["needrestart", "-b"]) {
+                    Some(output) if output.success() => {
+                        status.services =
+                            parse_needrestart_batch(&String::from_utf8_lossy(&output.stdout));
+                    }
+                    _ => status.scan_proc(root),
+                }
+            }
+            Self::Dnf => {
+                // needs-restarting -r exits with 1 when a reboot is required
+                status.reboot_required =
+                    run(&["dnf", "needs-restarting", "-r"]).is_some_and(|o| o.code == Some(1));
+                status.services = run(&["dnf", "needs-restarting", "-s"])
+                    .filter(|output| output.success())
+                    .map(|output| parse_service_list(&String::from_utf8_lossy(&output.stdout)))
+                    .unwrap_or_default();
+            }
+            Self::Zypper => {
+                // needs-rebooting exits with 102 when a reboot is required
+                status.reboot_required = root.join("run/reboot-needed").exists()
+                    || run(&["zypper", "needs-rebooting"]).is_some_and(|o| o.code == Some(102));
+                status.services = run(&["zypper", "ps", "-sss"])
+                    .filter(|output| output.success())
+                    .map(|output| parse_service_list(&String::from_utf8_lossy(&output.stdout)))
+                    .unwrap_or_default();
+            }
+            Self::Nix => {
+                status.reboot_required = nixos_generation_changed(root);
+                status.scan_proc(root);
+            }
+            Self::Pacman | Self::Apk | Self::Xbps | Self::Emerge | Self::Eopkg => {
+                // These remove or replace the running kernel's modules on upgrade
+                status.reboot_required =
+                    crate::system::running_kernel_modules_missing(root, kernel_release);
+                status.scan_proc(root);
+            }
+            // Sandboxed apps are restarted by the user, never the system
+            Self::Flatpak | Self::Snap => {}
+        }
+
+        let (critical, services): (Vec<String>, Vec<String>) = std::mem::take(&mut status.services)
+            .into_iter()
+            .partition(|service| is_session_critical(service));
+        if !critical.is_empty() {
+            tracing::info!("Session-critical services need a reboot: {:?}", critical);
+            status.reboot_required = true;
+        }
+        status.services = services;
+
+        tracing::debug!("{} restart status: {:?}", self, status);
+        status
+    }
+}
+
+/// Compare the booted NixOS generation with the current one. A reboot is needed
+/// when the kernel, its modules or the initrd differ.
+fn nixos_generation_changed(root: &Path) -> bool {
+    ["kernel", "kernel-modules", "initrd"].iter().any(|part| {
+        let booted = std::fs::read_link(root.join("run/booted-system").join(part));
+        let current = std::fs::read_link(root.join("run/current-system").join(part));
+        matches!((booted, current), (Ok(booted), Ok(current)) if booted != current)
+    })
+}
+
+/// Whether restarting `service` would break the running desktop session
+fn is_session_critical(service: &str) -> bool {
+    SESSION_CRITICAL_UNITS.contains(&service) || service.starts_with("user@")
+}
+
+/// Parse `needrestart -b`: `NEEDRESTART-SVC: ssh.service`
+fn parse_needrestart_batch(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("NEEDRESTART-SVC:"))
+        .map(|service| service.trim().to_string())
+        .filter(|service| !service.is_empty())
+        .collect()
+}
+
+/// Parse one-service-per-line output (`dnf needs-restarting -s`, `zypper ps -sss`)
+fn parse_service_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.contains(char::is_whitespace))
+        .map(|service| {
+            if service.contains('.') {
+                service.to_string()
+            } else {
+                format!("{}.service", service)
+            }
+        })
+        .collect()
+}
+
+fn non_empty_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::runner::FakeRunner;
+    use crate::test_util::FakeRoot;
+
+    #[test]
+    fn test_apt_reboot_required_file() {
+        let root = FakeRoot::new("restart-apt");
+        std::fs::create_dir_all(root.join("run")).unwrap();
+        std::fs::write(
+            root.join("run/reboot-required"),
+            "*** System restart required ***\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("run/reboot-required.pkgs"),
+            "linux-image-6.5.0-15-generic\nlibc6\n",
+        )
+        .unwrap();
+        let runner = FakeRunner::new().on(
+            &["needrestart", "-b"],
+            0,
+            "NEEDRESTART-VER: 3.6\nNEEDRESTART-KSTA: 3\nNEEDRESTART-SVC: ssh.service\nNEEDRESTART-SVC: cron.service\n",
+            "",
+        );
+
+        let status = PackageManager::Apt.restart_status_with(&runner, &root, "6.5.0-14-generic");
+        assert!(status.reboot_required);
+        assert_eq!(
+            status.reboot_packages,
+            ["linux-image-6.5.0-15-generic", "libc6"]
+        );
+        assert_eq!(status.services, ["ssh.service", "cron.service"]);
+    }
+
+    #[test]
+    fn test_dnf_needs_restarting() {
+        let root = FakeRoot::new("restart-dnf");
+        let runner = FakeRunner::new()
+            .on(
+                &["dnf", "needs-restarting", "-r"],
+                1,
+                "Core libraries or services have been updated since boot-up:\n  * kernel\n",
+                "",
+            )
+            .on(
+                &["dnf", "needs-restarting", "-s"],
+                0,
+                "sshd.service\nNetworkManager.service\n",
+                "",
+            );
+
+        let status =
+            PackageManager::Dnf.restart_status_with(&runner, &root, "6.6.8-200.fc39.x86_64");
+        assert!(status.reboot_required);
+        assert_eq!(status.services, ["sshd.service"]);
+
+        let runner = FakeRunner::new().on(&["dnf", "needs-restarting", "-r"], 0, "", "");
+        let status =
+            PackageManager::Dnf.restart_status_with(&runner, &root, "6.6.8-200.fc39.x86_64");
+        assert!(!status.needs_attention());
+    }
+
+    #[test]
+    fn test_zypper_needs_rebooting() {
+        let root = FakeRoot::new("restart-zypper");
+        let runner = FakeRunner::new()
+            .on(&["zypper", "needs-rebooting"], 102, "", "")
+            .on(&["zypper", "ps", "-sss"], 0, "cups\nsshd\n", "");
+
+        let status = PackageManager::Zypper.restart_status_with(&runner, &root, "6.7.1-1-default");
+        assert!(status.reboot_required);
+        assert_eq!(status.services, ["cups.service", "sshd.service"]);
+    }
+
+    #[test]
+    fn test_session_critical_services_need_reboot() {
+        let root = FakeRoot::new("restart-session");
+        let runner = FakeRunner::new()
+            .on(&["zypper", "needs-rebooting"], 0, "", "")
+            .on(
+                &["zypper", "ps", "-sss"],
+                0,
+                "dbus\nsystemd-logind\ncups\nuser@1000\nsddm\n",
+                "",
+            );
+
+        let status = PackageManager::Zypper.restart_status_with(&runner, &root, "6.7.1-1-default");
+        assert!(status.reboot_required);
+        assert_eq!(status.services, ["cups.service"]);
+    }
+
+    #[test]
+    fn test_pacman_running_kernel_modules_removed() {
+        let root = FakeRoot::new("restart-pacman");
+        std::fs::create_dir_all(root.join("usr/lib/modules/6.7.2-arch1-1")).unwrap();
+        let runner = FakeRunner::new();
+
+        let status = PackageManager::Pacman.restart_status_with(&runner, &root, "6.7.1-arch1-1");
+        assert!(status.reboot_required);
+
+        let status = PackageManager::Pacman.restart_status_with(&runner, &root, "6.7.2-arch1-1");
+        assert!(!status.reboot_required);
+    }
+
+    #[test]
+    fn test_nixos_generation_changed() {
+        let root = FakeRoot::new("restart-nix");
+        for (system, kernel) in [
+            ("booted-system", "/nix/store/aaa-linux-6.6.10"),
+            ("current-system", "/nix/store/bbb-linux-6.6.11"),
+        ] {
+            let dir = root.join("run").join(system);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::os::unix::fs::symlink(kernel, dir.join("kernel")).unwrap();
+        }
+
+        let status = PackageManager::Nix.restart_status_with(&FakeRunner::new(), &root, "6.6.10");
+        assert!(status.reboot_required);
+    }
+
+    #[test]
+    fn test_secondary_sources_never_need_reboot() {
+        let root = FakeRoot::new("restart-flatpak");
+        let status =
+            PackageManager::Flatpak.restart_status_with(&FakeRunner::new(), &root, "6.6.10");
+        assert_eq!(status, RestartStatus::default());
+    }
+}
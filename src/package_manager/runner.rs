@@ -0,0 +1,241 @@
+use crate::error::{Result, RoStartError};
+use std::future::Future;
+use std::io::{self, BufRead, BufReader, Read};
+use std::pin::Pin;
+use std::process::{Command, Stdio};
+
+/// Boxed future returned by `CommandRunner::run_async`
+pub type OutputFuture = Pin<Box<dyn Future<Output = io::Result<CommandOutput>> + Send>>;
+
+/// Captured result of a finished command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// Exit code, `None` when the process was killed by a signal
+    pub code: Option<i32>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+impl From<std::process::Output> for CommandOutput {
+    fn from(output: std::process::Output) -> Self {
+        Self {
+            code: output.status.code(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        }
+    }
+}
+
+/// Executes external commands for the package manager backends.
+///
+/// `cmd[0]` is the program and the rest are its arguments. A missing binary is
+/// reported as an `io::ErrorKind::NotFound` error.
+pub trait CommandRunner: Send + Sync {
+    /// Run a command to completion and capture its output
+    fn run(&self, cmd: &[String]) -> io::Result<CommandOutput>;
+
+    /// Run a command on the Tokio runtime. Dropping the future kills the process.
+    fn run_async(&self, cmd: &[String]) -> OutputFuture;
+
+    /// Run a command, passing every stdout/stderr line to `on_line` as it is
+    /// printed, and return its exit code
+    fn run_streaming(
+        &self,
+        cmd: &[String],
+        on_line: &mut dyn FnMut(String),
+    ) -> io::Result<Option<i32>>;
+}
+
+/// Runs commands on the real system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, cmd: &[String]) -> io::Result<CommandOutput> {
+        // Force the C locale so the output format does not depend on the user's language
+        Command::new(&cmd[0])
+            .args(&cmd[1..])
+            .env("LC_ALL", "C")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .map(CommandOutput::from)
+    }
+
+    fn run_async(&self, cmd: &[String]) -> OutputFuture {
+        let child = tokio::process::Command::new(&cmd[0])
+            .args(&cmd[1..])
+            .env("LC_ALL", "C")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+
+        Box::pin(async move { Ok(child?.wait_with_output().await?.into()) })
+    }
+
+    fn run_streaming(
+        &self,
+        cmd: &[String],
+        on_line: &mut dyn FnMut(String),
+    ) -> io::Result<Option<i32>> {
+        let mut child = Command::new(&cmd[0])
+            .args(&cmd[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Read both pipes on their own threads so neither can fill up and stall the child
+        let (tx, rx) = std::sync::mpsc::channel();
+        let readers: Vec<_> = [
+            child
+                .stdout
+                .take()
+                .map(|out| Box::new(out) as Box<dyn Read + Send>),
+            child
+                .stderr
+                .take()
+                .map(|err| Box::new(err) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|pipe| {
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                for line in BufReader::new(pipe).lines().map_while(|l| l.ok()) {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+        drop(tx);
+
+        for line in rx {
+            on_line(line);
+        }
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        Ok(child.wait()?.code())
+    }
+}
+
+/// Run a command and return its stdout, failing on a non-zero exit
+pub fn run_stdout(runner: &impl CommandRunner, cmd: &[impl AsRef<str>]) -> Result<String> {
+    let cmd: Vec<String> = cmd.iter().map(|arg| arg.as_ref().to_string()).collect();
+    let output = runner
+        .run(&cmd)
+        .map_err(|e| RoStartError::CommandFailed(format!("Failed to run {}: {}", cmd[0], e)))?;
+    if !output.success() {
+        return Err(RoStartError::CommandFailed(format!(
+            "{} exited with {:?}: {}",
+            cmd[0],
+            output.code,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Test double that replays canned outputs and records every command it was asked
+/// to run. Commands without a canned response behave like a missing binary.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct FakeRunner {
+    responses: std::collections::HashMap<Vec<String>, FakeResponse>,
+    calls: std::sync::Mutex<Vec<Vec<String>>>,
+}
+
+#[cfg(test)]
+#[derive(Debug, Clone)]
+struct FakeResponse {
+    output: CommandOutput,
+    /// Simulated run time for `run_async`, used to exercise timeouts
+    delay: std::time::Duration,
+}
+
+#[cfg(test)]
+impl FakeRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reply to `cmd` with the given exit code, stdout and stderr
+    pub fn on(mut self, cmd: &[&str], code: i32, stdout: &str, stderr: &str) -> Self {
+        self.responses.insert(
+            cmd.iter().map(|s| s.to_string()).collect(),
+            FakeResponse {
+                output: CommandOutput {
+                    code: Some(code),
+                    stdout: stdout.as_bytes().to_vec(),
+                    stderr: stderr.as_bytes().to_vec(),
+                },
+                delay: std::time::Duration::ZERO,
+            },
+        );
+        self
+    }
+
+    /// Make `run_async` for `cmd` take `delay` before replying
+    pub fn delayed(mut self, cmd: &[&str], delay: std::time::Duration) -> Self {
+        let key: Vec<String> = cmd.iter().map(|s| s.to_string()).collect();
+        if let Some(response) = self.responses.get_mut(&key) {
+            response.delay = delay;
+        }
+        self
+    }
+
+    /// Every command run so far, in order
+    pub fn calls(&self) -> Vec<Vec<String>> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn respond(&self, cmd: &[String]) -> io::Result<FakeResponse> {
+        self.calls.lock().unwrap().push(cmd.to_vec());
+        self.responses.get(cmd).cloned().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("{}: not found", cmd[0]))
+        })
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for FakeRunner {
+    fn run(&self, cmd: &[String]) -> io::Result<CommandOutput> {
+        self.respond(cmd).map(|response| response.output)
+    }
+
+    fn run_async(&self, cmd: &[String]) -> OutputFuture {
+        let response = self.respond(cmd);
+        Box::pin(async move {
+            let response = response?;
+            tokio::time::sleep(response.delay).await;
+            Ok(response.output)
+        })
+    }
+
+    fn run_streaming(
+        &self,
+        cmd: &[String],
+        on_line: &mut dyn FnMut(String),
+    ) -> io::Result<Option<i32>> {
+        let output = self.run(cmd)?;
+        for stream in [&output.stdout, &output.stderr] {
+            String::from_utf8_lossy(stream)
+                .lines()
+                .for_each(|line| on_line(line.to_string()));
+        }
+        Ok(output.code)
+    }
+}
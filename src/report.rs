@@ -0,0 +1,562 @@
+//! System report for support requests (`ro-start report`)
+//!
+//! Bundles what the forum usually asks for (system and distribution, session,
+//! package managers and pending updates, GPUs, disk usage and recent journal
+//! errors) into Markdown, JSON, or a `.tar.gz` holding both.
+
+use crate::drivers::Gpu;
+use crate::package_manager::runner::{CommandRunner, SystemRunner};
+use crate::package_manager::{PackageManager, UpdateCheckOptions, UpdateInfo};
+use crate::services::JournalEntry;
+use crate::system::{SystemInfo, SystemState};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Journal lines of priority "err" or worse included in a report
+const JOURNAL_LINES: usize = 50;
+
+/// Base name of the report files, also the directory inside the archive
+const REPORT_NAME: &str = "ro-start-report";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Json,
+    /// Archive with the Markdown and JSON reports
+    TarGz,
+}
+
+/// Everything a support request needs. Serialized as the JSON report.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub schema_version: u32,
+    /// Unix time the report was collected
+    pub generated_at: u64,
+    pub ro_start_version: String,
+    pub system: SystemInfo,
+    pub package_managers: Vec<PackageManager>,
+    pub updates: Option<UpdateInfo>,
+    /// Why `updates` is missing
+    pub update_error: Option<String>,
+    pub gpus: Vec<Gpu>,
+    /// Recent `journalctl -p err` entries of the current boot
+    pub journal_errors: Vec<JournalEntry>,
+    /// Whether personal details were replaced
+    pub redacted: bool,
+}
+
+impl Report {
+    /// Gather the report; the update check is aborted after `timeout`.
+    ///
+    /// Must run on the background runtime (see `crate::runtime`). Everything but
+    /// the update check blocks, so it runs on the blocking thread pool; the
+    /// graphics and journal details are read while the check is in progress.
+    pub async fn collect(timeout: Duration) -> Self {
+        let (system, package_managers) = tokio::task::spawn_blocking(|| {
+            (
+                SystemState::new().get_system_info(),
+                PackageManager::detect_all(),
+            )
+        })
+        .await
+        .expect("Collecting system details panicked");
+
+        let details = tokio::task::spawn_blocking(|| {
+            let journal_errors =
+                crate::services::recent_errors(JOURNAL_LINES).unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read the journal: {}", e);
+                    Vec::new()
+                });
+            (crate::drivers::detect_gpus(), journal_errors)
+        });
+
+        let options = UpdateCheckOptions {
+            timeout,
+            ..Default::default()
+        };
+        let (updates, update_error) = if package_managers.is_empty() {
+            (
+                None,
+                Some(crate::error::RoStartError::PackageManagerNotFound.to_string()),
+            )
+        } else {
+            match PackageManager::check_all_updates_async(&package_managers, options, |_| {}).await
+            {
+                Ok(info) => (Some(info), None),
+                Err(e) => (None, Some(e.to_string())),
+            }
+        };
+
+        let (gpus, journal_errors) = details.await.expect("Collecting report details panicked");
+
+        Self {
+            schema_version: crate::output::SCHEMA_VERSION,
+            generated_at: crate::config::unix_now(),
+            ro_start_version: env!("CARGO_PKG_VERSION").to_string(),
+            system,
+            package_managers,
+            updates,
+            update_error,
+            gpus,
+            journal_errors,
+            redacted: false,
+        }
+    }
+
+    /// Replace the hostname, the user name and IP addresses
+    pub fn redact(&mut self, redaction: &Redaction) {
+        self.system.hostname = "[hostname]".to_string();
+        for disk in &mut self.system.disks {
+            disk.mount_point = redaction.apply(&disk.mount_point);
+        }
+        for entry in &mut self.journal_errors {
+            entry.message = redaction.apply(&entry.message);
+        }
+        self.redacted = true;
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let system = &self.system;
+        let os = &system.os_release;
+        let mut md = String::new();
+
+        let _ = writeln!(md, "# Ro-Start System Report\n");
+        let _ = writeln!(md, "Generated by Ro-Start {}.", self.ro_start_version);
+        if self.redacted {
+            let _ = writeln!(md, "Hostname, user name and IP addresses are redacted.");
+        }
+
+        let _ = writeln!(md, "\n## System\n");
+        let _ = writeln!(md, "| | |\n| --- | --- |");
+        let rows = [
+            ("Distribution", os.pretty_name.clone()),
+            (
+                "ID",
+                Some(os.ids().collect::<Vec<_>>().join(" ")).filter(|ids| !ids.is_empty()),
+            ),
+            ("Version", os.version.clone()),
+            ("Kernel", Some(system.kernel_version.clone())),
+            ("Desktop", Some(system.desktop_environment.clone())),
+            ("Session", Some(system.session_summary())),
+            ("Hostname", Some(system.hostname.clone())),
+            ("CPU", Some(system.cpu_name.clone())),
+            ("Memory", Some(system.memory_info.clone())),
+        ];
+        for (key, value) in rows {
+            if let Some(value) = value {
+                let _ = writeln!(md, "| {} | {} |", key, value.replace('|', "\\|"));
+            }
+        }
+
+        let _ = writeln!(md, "\n## Storage\n");
+        for disk in &system.disks {
+            let low = if disk.is_low_on_space() {
+                " **(low on space)**"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                md,
+                "- `{}` ({}, {}): {}{}",
+                disk.mount_point,
+                disk.device,
+                disk.file_system,
+                disk.usage_summary(),
+                low
+            );
+        }
+
+        let _ = writeln!(md, "\n## Graphics\n");
+        if self.gpus.is_empty() {
+            let _ = writeln!(md, "No GPU detected.");
+        }
+        for gpu in &self.gpus {
+            let _ = writeln!(
+                md,
+                "- {} [{:04x}:{:04x}], driver: {}",
+                gpu.name(),
+                gpu.vendor_id,
+                gpu.device_id,
+                gpu.driver.as_deref().unwrap_or("none")
+            );
+        }
+
+        let _ = writeln!(md, "\n## Updates\n");
+        let backends: Vec<String> = self
+            .package_managers
+            .iter()
+            .map(|pm| pm.to_string())
+            .collect();
+        let _ = writeln!(md, "Package managers: {}\n", backends.join(", "));
+        match (&self.updates, &self.update_error) {
+            (Some(info), _) => {
+                let _ = writeln!(md, "{}", info.message());
+                for source in info.sources.iter().filter(|s| !s.updates.is_empty()) {
+                    let _ = writeln!(
+                        md,
+                        "- {}: {} pending",
+                        source.package_manager,
+                        source.updates.len()
+                    );
+                }
+            }
+            (None, Some(error)) => {
+                let _ = writeln!(md, "Update check failed: {}", error);
+            }
+            (None, None) => {}
+        }
+
+        let _ = writeln!(md, "\n## Recent Errors\n");
+        if self.journal_errors.is_empty() {
+            let _ = writeln!(md, "No errors logged since boot.");
+        } else {
+            let _ = writeln!(md, "```");
+            for entry in &self.journal_errors {
+                let _ = writeln!(md, "{}", entry);
+            }
+            let _ = writeln!(md, "```");
+        }
+        md
+    }
+
+    /// Write the report to `path`, or to standard output when `path` is `None`.
+    /// Archives always need a path.
+    pub fn write(&self, format: ReportFormat, path: Option<&Path>) -> Result<()> {
+        let contents = match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Json => self.to_json()?,
+            ReportFormat::TarGz => {
+                let Some(path) = path else {
+                    bail!("An output file is required for tar-gz reports");
+                };
+                return self.write_archive_with(&SystemRunner, path);
+            }
+        };
+        match path {
+            Some(path) => std::fs::write(path, contents)
+                .with_context(|| format!("Failed to write {}", path.display())),
+            None => {
+                print!("{}", contents);
+                Ok(())
+            }
+        }
+    }
+
+    /// Pack the Markdown and JSON reports into a `.tar.gz` with `tar`
+    fn write_archive_with(&self, runner: &impl CommandRunner, path: &Path) -> Result<()> {
+        // The unredacted files must not be readable by or redirected to other
+        // local users, so stage them in a fresh private directory
+        let base = dirs::runtime_dir().unwrap_or_else(std::env::temp_dir);
+        let staging = create_private_dir(&base)
+            .with_context(|| format!("Failed to create a directory in {}", base.display()))?;
+
+        let result = self.pack_archive(runner, path, &staging);
+        let _ = std::fs::remove_dir_all(&staging);
+        result
+    }
+
+    /// Write both reports into `staging` and pack them with `tar`
+    fn pack_archive(&self, runner: &impl CommandRunner, path: &Path, staging: &Path) -> Result<()> {
+        let dir = staging.join(REPORT_NAME);
+        std::fs::create_dir(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        std::fs::write(dir.join(format!("{}.md", REPORT_NAME)), self.to_markdown())?;
+        std::fs::write(dir.join(format!("{}.json", REPORT_NAME)), self.to_json()?)?;
+
+        let cmd: Vec<String> = vec![
+            "tar".into(),
+            "-czf".into(),
+            path.to_string_lossy().into_owned(),
+            "-C".into(),
+            staging.to_string_lossy().into_owned(),
+            REPORT_NAME.into(),
+        ];
+        let output = runner.run(&cmd).context("Failed to run tar")?;
+        if !output.success() {
+            bail!(
+                "tar failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Create a new directory with an unpredictable name in `base`, readable only
+/// by the current user. Never reuses an existing directory.
+fn create_private_dir(base: &Path) -> std::io::Result<PathBuf> {
+    use std::hash::{BuildHasher, Hasher};
+    use std::os::unix::fs::DirBuilderExt;
+
+    let mut attempts = 0;
+    loop {
+        // RandomState is seeded from the OS random number generator
+        let suffix = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let dir = base.join(format!("{}-{:016x}", REPORT_NAME, suffix));
+        match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempts < 8 => {
+                attempts += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Where the desktop app saves report archives
+pub fn default_archive_path() -> PathBuf {
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!(
+            "{}-{}.tar.gz",
+            REPORT_NAME,
+            crate::config::unix_now()
+        ))
+}
+
+/// Personal details to remove from a report
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    pub hostname: String,
+    pub username: Option<String>,
+}
+
+impl Redaction {
+    /// The details of the running system and user
+    pub fn for_system(info: &SystemInfo) -> Self {
+        Self {
+            hostname: info.hostname.clone(),
+            username: std::env::var("USER").ok().or_else(|| {
+                dirs::home_dir()?
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            }),
+        }
+    }
+
+    /// Replace the hostname, the user name and IPv4/IPv6 addresses in `text`
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = replace_word(text, &self.hostname, "[hostname]");
+        if let Some(username) = &self.username {
+            text = replace_word(&text, username, "[user]");
+        }
+        redact_ip_addresses(&text)
+    }
+}
+
+/// Replace `word` where it is not part of a longer name
+fn replace_word(text: &str, word: &str, replacement: &str) -> String {
+    if word.is_empty() {
+        return text.to_string();
+    }
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(word) {
+        let (before, after) = (&rest[..index], &rest[index + word.len()..]);
+        let bounded = !before.chars().next_back().is_some_and(is_name_char)
+            && !after.chars().next().is_some_and(is_name_char);
+        result.push_str(before);
+        result.push_str(if bounded { replacement } else { word });
+        rest = after;
+    }
+    result.push_str(rest);
+    result
+}
+
+fn redact_ip_addresses(text: &str) -> String {
+    let is_address_char = |c: char| c.is_ascii_hexdigit() || c == '.' || c == ':';
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_address_char) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_address_char(c)).unwrap_or(rest.len());
+        let (run, after) = rest.split_at(end);
+        // Sentence punctuation is not part of the address
+        let trimmed = run.trim_end_matches(['.', ':']);
+        let suffix = &run[trimmed.len()..];
+
+        if trimmed.contains(':') && trimmed.parse::<Ipv6Addr>().is_ok() {
+            result.push_str("[ip]");
+        } else {
+            // IPv4 addresses, possibly followed by a port
+            let parts: Vec<&str> = trimmed
+                .split(':')
+                .map(|part| {
+                    if part.parse::<Ipv4Addr>().is_ok() {
+                        "[ip]"
+                    } else {
+                        part
+                    }
+                })
+                .collect();
+            result.push_str(&parts.join(":"));
+        }
+        result.push_str(suffix);
+        rest = after;
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::runner::FakeRunner;
+    use crate::test_util::FakeRoot;
+
+    fn sample_report() -> Report {
+        let mut system = SystemState::new().get_system_info();
+        system.hostname = "alice-laptop".to_string();
+
+        Report {
+            schema_version: crate::output::SCHEMA_VERSION,
+            generated_at: 0,
+            ro_start_version: "2.0.0".to_string(),
+            system,
+            package_managers: vec![PackageManager::Apt],
+            updates: None,
+            update_error: Some("Update check timed out after 60 seconds".to_string()),
+            gpus: Vec::new(),
+            journal_errors: [
+                ("sshd", "error: connect from 192.168.1.20:51234 failed"),
+                (
+                    "NetworkManager",
+                    "dhcp6: lease fe80::1ff:fe23:4567:890a expired.",
+                ),
+                (
+                    "kernel",
+                    "/home/alice/.cache on alice-laptop not writable by alice2",
+                ),
+            ]
+            .map(|(identifier, message)| JournalEntry {
+                timestamp_usec: 1_760_771_521_000_000,
+                priority: 3,
+                unit: None,
+                scope: Default::default(),
+                identifier: Some(identifier.to_string()),
+                pid: None,
+                message: message.to_string(),
+            })
+            .to_vec(),
+            redacted: false,
+        }
+    }
+
+    #[test]
+    fn test_redaction() {
+        let mut report = sample_report();
+        report.redact(&Redaction {
+            hostname: "alice-laptop".to_string(),
+            username: Some("alice".to_string()),
+        });
+
+        assert_eq!(report.system.hostname, "[hostname]");
+        let messages: Vec<&str> = report
+            .journal_errors
+            .iter()
+            .map(|entry| entry.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "error: connect from [ip]:51234 failed",
+                "dhcp6: lease [ip] expired.",
+                "/home/[user]/.cache on [hostname] not writable by alice2",
+            ]
+        );
+
+        let json = report.to_json().unwrap();
+        assert!(!json.contains("alice-laptop"));
+        assert!(!json.contains("192.168"));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["redacted"], true);
+        assert_eq!(value["package_managers"][0], "apt");
+    }
+
+    #[test]
+    fn test_redaction_keeps_versions_and_times() {
+        let text = "Xorg 1.21.1 started at 09:12:01, kernel 6.8.0-45";
+        assert_eq!(redact_ip_addresses(text), text);
+        assert_eq!(
+            redact_ip_addresses("DNS 10.0.0.1, 2001:db8::1."),
+            "DNS [ip], [ip]."
+        );
+    }
+
+    #[test]
+    fn test_markdown_sections() {
+        let md = sample_report().to_markdown();
+
+        assert!(md.starts_with("# Ro-Start System Report\n"));
+        for section in [
+            "## System",
+            "## Storage",
+            "## Graphics",
+            "## Updates",
+            "## Recent Errors",
+        ] {
+            assert!(md.contains(section), "missing {}", section);
+        }
+        assert!(md.contains("| Hostname | alice-laptop |"));
+        assert!(md.contains("Update check failed: Update check timed out after 60 seconds"));
+        let time = glib::DateTime::from_unix_local(1_760_771_521)
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S")
+            .unwrap();
+        assert!(md.contains(&format!("```\n{} sshd: error: connect from", time)));
+    }
+
+    #[test]
+    fn test_archive_is_packed_with_tar() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let base = FakeRoot::new("report-staging");
+        let staging = create_private_dir(&base).unwrap();
+        let mode = std::fs::metadata(&staging).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        assert_ne!(create_private_dir(&base).unwrap(), staging);
+
+        let staging_arg = staging.to_string_lossy();
+        let tar = [
+            "tar",
+            "-czf",
+            "/tmp/report.tar.gz",
+            "-C",
+            &staging_arg,
+            REPORT_NAME,
+        ];
+        let path = Path::new("/tmp/report.tar.gz");
+
+        let runner = FakeRunner::new().on(&tar, 0, "", "");
+        sample_report()
+            .pack_archive(&runner, path, &staging)
+            .unwrap();
+        assert_eq!(runner.calls(), [tar.map(String::from)]);
+        assert!(staging
+            .join(REPORT_NAME)
+            .join("ro-start-report.md")
+            .exists());
+        std::fs::remove_dir_all(staging.join(REPORT_NAME)).unwrap();
+
+        let runner = FakeRunner::new().on(&tar, 2, "", "tar: /tmp: Cannot open\n");
+        let error = sample_report()
+            .pack_archive(&runner, path, &staging)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "tar failed: tar: /tmp: Cannot open");
+    }
+}
@@ -0,0 +1,42 @@
+use lazy_static::lazy_static;
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+use tokio::task::JoinHandle;
+
+lazy_static! {
+    /// Background runtime for process and timer work; GTK stays on its own main loop
+    static ref RUNTIME: Runtime = Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("ro-start-worker")
+        .enable_all()
+        .build()
+        .expect("Failed to create Tokio runtime");
+}
+
+/// Spawn a future on the background runtime.
+///
+/// The returned handle can be awaited from the GTK main loop (e.g. inside
+/// `glib::spawn_future_local`) without blocking it.
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    RUNTIME.spawn(future)
+}
+
+/// Run blocking work (file scans, synchronous commands) on the background runtime's
+/// blocking thread pool
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    RUNTIME.spawn_blocking(f)
+}
+
+/// Drive a future to completion on the background runtime from a thread that is
+/// not part of it. Used by the headless modes that have no GTK main loop.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME.block_on(future)
+}
@@ -0,0 +1,391 @@
+//! Failed systemd units and recent journal errors
+//!
+//! Parses `systemctl --failed --output=json` and `journalctl -p err -o json`
+//! for the system and the user session. Commands go through a
+//! `CommandRunner` so the parsers are tested against captured output in
+//! `fixtures/`.
+
+use crate::error::{Result, RoStartError};
+use crate::package_manager::runner::{run_stdout, CommandRunner, SystemRunner};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Journal lines shown by "View Log"
+const UNIT_LOG_LINES: usize = 200;
+
+/// Whether a unit belongs to the system or to the user's session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitScope {
+    #[default]
+    System,
+    User,
+}
+
+impl UnitScope {
+    /// `systemctl` and `journalctl` flag selecting the user manager
+    fn user_flag(self) -> Option<&'static str> {
+        match self {
+            Self::System => None,
+            Self::User => Some("--user"),
+        }
+    }
+}
+
+/// A unit in the "failed" state
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FailedUnit {
+    /// Unit name, e.g. `cups.service`
+    pub unit: String,
+    /// "loaded", "not-found", ...
+    pub load: String,
+    pub active: String,
+    pub sub: String,
+    pub description: String,
+    #[serde(skip_deserializing)]
+    pub scope: UnitScope,
+}
+
+/// One journal entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JournalEntry {
+    /// Microseconds since the Unix epoch
+    pub timestamp_usec: u64,
+    /// syslog priority, 0 (emerg) to 3 (err) for the entries collected here
+    pub priority: u8,
+    /// Unit the entry is about or was logged by
+    pub unit: Option<String>,
+    pub scope: UnitScope,
+    /// `SYSLOG_IDENTIFIER`, falling back to the command name
+    pub identifier: Option<String>,
+    pub pid: Option<u32>,
+    pub message: String,
+}
+
+impl fmt::Display for JournalEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Local time, like journalctl prints it
+        let time = glib::DateTime::from_unix_local((self.timestamp_usec / 1_000_000) as i64)
+            .and_then(|time| time.format("%Y-%m-%d %H:%M:%S"))
+            .map(|time| time.to_string())
+            .unwrap_or_default();
+        write!(f, "{}", time)?;
+        if let Some(identifier) = &self.identifier {
+            write!(f, " {}", identifier)?;
+            if let Some(pid) = self.pid {
+                write!(f, "[{}]", pid)?;
+            }
+            write!(f, ":")?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
+
+/// Failed units of the system or user manager
+pub fn failed_units(scope: UnitScope) -> Result<Vec<FailedUnit>> {
+    failed_units_with(&SystemRunner, scope)
+}
+
+/// `failed_units` using `runner` to run `systemctl`
+pub fn failed_units_with(runner: &impl CommandRunner, scope: UnitScope) -> Result<Vec<FailedUnit>> {
+    let mut cmd = vec!["systemctl".to_string()];
+    cmd.extend(scope.user_flag().map(String::from));
+    cmd.extend(["--failed", "--output=json", "--no-pager"].map(String::from));
+
+    let stdout = run_stdout(runner, &cmd)?;
+    parse_failed_units(&stdout, scope)
+}
+
+/// Parse the JSON array printed by `systemctl --failed --output=json`
+pub fn parse_failed_units(json: &str, scope: UnitScope) -> Result<Vec<FailedUnit>> {
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut units: Vec<FailedUnit> = serde_json::from_str(json)
+        .map_err(|e| RoStartError::CommandFailed(format!("Invalid systemctl output: {}", e)))?;
+    for unit in &mut units {
+        unit.scope = scope;
+    }
+    Ok(units)
+}
+
+/// The last `limit` entries of priority "err" or worse since boot
+pub fn recent_errors(limit: usize) -> Result<Vec<JournalEntry>> {
+    recent_errors_with(&SystemRunner, limit)
+}
+
+/// `recent_errors` using `runner` to run `journalctl`
+pub fn recent_errors_with(runner: &impl CommandRunner, limit: usize) -> Result<Vec<JournalEntry>> {
+    let limit = limit.to_string();
+    let cmd: Vec<String> = [
+        "journalctl",
+        "-b",
+        "-p",
+        "err",
+        "-o",
+        "json",
+        "-n",
+        &limit,
+        "-q",
+        "--no-pager",
+    ]
+    .map(String::from)
+    .to_vec();
+
+    Ok(parse_journal(&run_stdout(runner, &cmd)?))
+}
+
+/// Parse `journalctl -o json` output, one object per line. Lines that are not
+/// valid entries are skipped.
+pub fn parse_journal(output: &str) -> Vec<JournalEntry> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line)
+                .map_err(|e| tracing::debug!("Skipping journal line: {}", e))
+                .ok()?;
+            let field = |name: &str| journal_field(&value, name);
+
+            // Messages from systemd about a unit carry it in UNIT/USER_UNIT
+            let (unit, scope) = match (
+                field("UNIT"),
+                field("USER_UNIT").or_else(|| field("_SYSTEMD_USER_UNIT")),
+                field("_SYSTEMD_UNIT"),
+            ) {
+                (Some(unit), _, _) => (Some(unit), UnitScope::System),
+                (None, Some(unit), _) => (Some(unit), UnitScope::User),
+                (None, None, unit) => (unit, UnitScope::System),
+            };
+
+            Some(JournalEntry {
+                timestamp_usec: field("__REALTIME_TIMESTAMP")?.parse().ok()?,
+                priority: field("PRIORITY").and_then(|p| p.parse().ok()).unwrap_or(3),
+                unit,
+                scope,
+                identifier: field("SYSLOG_IDENTIFIER").or_else(|| field("_COMM")),
+                pid: field("_PID").and_then(|pid| pid.parse().ok()),
+                message: field("MESSAGE").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// A journal field as text. Fields that are not valid UTF-8 are exported as
+/// an array of bytes.
+fn journal_field(entry: &serde_json::Value, name: &str) -> Option<String> {
+    match entry.get(name)? {
+        serde_json::Value::String(value) => Some(value.clone()),
+        serde_json::Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|byte| byte.as_u64())
+                .map(|byte| byte as u8)
+                .collect();
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => None,
+    }
+}
+
+/// The unit's log since boot
+pub fn unit_log(unit: &str, scope: UnitScope) -> Result<String> {
+    unit_log_with(&SystemRunner, unit, scope)
+}
+
+/// `unit_log` using `runner` to run `journalctl`
+pub fn unit_log_with(runner: &impl CommandRunner, unit: &str, scope: UnitScope) -> Result<String> {
+    let unit_flag = match scope {
+        UnitScope::System => "--unit",
+        UnitScope::User => "--user-unit",
+    };
+    let lines = UNIT_LOG_LINES.to_string();
+    let cmd: Vec<String> = [
+        "journalctl",
+        unit_flag,
+        unit,
+        "-b",
+        "-n",
+        &lines,
+        "-o",
+        "short-iso",
+        "-q",
+        "--no-pager",
+    ]
+    .map(String::from)
+    .to_vec();
+
+    run_stdout(runner, &cmd)
+}
+
+/// Restart units with a single `systemctl` call, through pkexec for system
+/// units, and return the exit code
+pub fn restart_units(units: &[String], scope: UnitScope) -> Result<i32> {
+    restart_units_with(&SystemRunner, units, scope)
+}
+
+/// `restart_units` using `runner` to run `systemctl`
+pub fn restart_units_with(
+    runner: &impl CommandRunner,
+    units: &[String],
+    scope: UnitScope,
+) -> Result<i32> {
+    let mut cmd: Vec<String> = match scope {
+        UnitScope::System => vec!["pkexec", "systemctl", "restart"],
+        UnitScope::User => vec!["systemctl", "--user", "restart"],
+    }
+    .into_iter()
+    .map(String::from)
+    .collect();
+    cmd.extend_from_slice(units);
+    tracing::info!("Restarting units: {}", cmd.join(" "));
+
+    let output = runner.run(&cmd).map_err(|e| {
+        tracing::error!("Failed to run {:?}: {}", cmd, e);
+        RoStartError::CommandFailed(format!("Failed to restart {}: {}", units.join(", "), e))
+    })?;
+    Ok(output.code.unwrap_or(-1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package_manager::runner::FakeRunner;
+
+    const FAILED_FIXTURE: &str = include_str!("fixtures/systemctl-failed.json");
+    const USER_FAILED_FIXTURE: &str = include_str!("fixtures/systemctl-user-failed.json");
+    const JOURNAL_FIXTURE: &str = include_str!("fixtures/journalctl-err.json");
+
+    #[test]
+    fn test_parse_failed_units() {
+        let units = parse_failed_units(FAILED_FIXTURE, UnitScope::System).unwrap();
+        assert_eq!(units.len(), 3);
+        assert_eq!(units[0].unit, "cups.service");
+        assert_eq!(units[0].description, "CUPS Scheduler");
+        assert_eq!(units[2].load, "not-found");
+        assert!(units.iter().all(|unit| unit.scope == UnitScope::System));
+
+        assert!(parse_failed_units("[]", UnitScope::System)
+            .unwrap()
+            .is_empty());
+        assert!(parse_failed_units("not json", UnitScope::System).is_err());
+    }
+
+    #[test]
+    fn test_failed_units_per_scope() {
+        let runner = FakeRunner::new()
+            .on(
+                &["systemctl", "--failed", "--output=json", "--no-pager"],
+                0,
+                FAILED_FIXTURE,
+                "",
+            )
+            .on(
+                &[
+                    "systemctl",
+                    "--user",
+                    "--failed",
+                    "--output=json",
+                    "--no-pager",
+                ],
+                0,
+                USER_FAILED_FIXTURE,
+                "",
+            );
+
+        assert_eq!(
+            failed_units_with(&runner, UnitScope::System).unwrap().len(),
+            3
+        );
+        let user = failed_units_with(&runner, UnitScope::User).unwrap();
+        assert_eq!(user[0].unit, "pipewire-pulse.service");
+        assert_eq!(user[0].scope, UnitScope::User);
+
+        assert!(failed_units_with(&FakeRunner::new(), UnitScope::System).is_err());
+    }
+
+    #[test]
+    fn test_parse_journal() {
+        let entries = parse_journal(&format!("{}\nnot json\n", JOURNAL_FIXTURE));
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(entries[0].identifier.as_deref(), Some("kernel"));
+        assert_eq!(entries[0].unit, None);
+        assert_eq!(entries[0].timestamp_usec, 1_760_771_521_123_456);
+
+        assert_eq!(entries[1].unit.as_deref(), Some("cups.service"));
+        assert_eq!(entries[1].pid, Some(1234));
+
+        // systemd's own message about the unit
+        assert_eq!(entries[2].priority, 2);
+        assert_eq!(entries[2].identifier.as_deref(), Some("systemd"));
+        assert_eq!(entries[2].unit.as_deref(), Some("cups.service"));
+
+        // User unit with a message that is not valid UTF-8
+        assert_eq!(entries[3].unit.as_deref(), Some("pipewire-pulse.service"));
+        assert_eq!(entries[3].scope, UnitScope::User);
+        assert_eq!(entries[3].message, "mod-protocol-pulse: bad byte \u{fffd}");
+
+        let time = glib::DateTime::from_unix_local(1_760_771_538)
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S")
+            .unwrap();
+        assert_eq!(
+            entries[1].to_string(),
+            format!("{} cupsd[1234]: Unable to open listen socket for address [v1.::1]:631 - Cannot assign requested address.", time)
+        );
+    }
+
+    #[test]
+    fn test_unit_actions() {
+        let runner = FakeRunner::new()
+            .on(
+                &[
+                    "journalctl",
+                    "--user-unit",
+                    "pipewire-pulse.service",
+                    "-b",
+                    "-n",
+                    "200",
+                    "-o",
+                    "short-iso",
+                    "-q",
+                    "--no-pager",
+                ],
+                0,
+                "2025-10-18T09:12:56+0200 workstation pipewire-pulse[2211]: bad byte\n",
+                "",
+            )
+            .on(
+                &["pkexec", "systemctl", "restart", "cups.service"],
+                0,
+                "",
+                "",
+            )
+            .on(
+                &["systemctl", "--user", "restart", "pipewire-pulse.service"],
+                1,
+                "",
+                "",
+            );
+
+        assert!(
+            unit_log_with(&runner, "pipewire-pulse.service", UnitScope::User)
+                .unwrap()
+                .contains("bad byte")
+        );
+        assert_eq!(
+            restart_units_with(&runner, &["cups.service".to_string()], UnitScope::System).unwrap(),
+            0
+        );
+        assert_eq!(
+            restart_units_with(
+                &runner,
+                &["pipewire-pulse.service".to_string()],
+                UnitScope::User
+            )
+            .unwrap(),
+            1
+        );
+    }
+}
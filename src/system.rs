@@ -1,27 +1,285 @@
-use sysinfo::System;
+use crate::os_release::OsRelease;
+use crate::package_manager::runner::{CommandRunner, SystemRunner};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
 
-#[derive(Debug, Clone)]
+/// System details shown on the home page. Serialized as the `system` section of
+/// the versioned output schema (see `crate::output`).
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct SystemInfo {
     pub cpu_name: String,
     #[allow(dead_code)]
+    #[serde(rename = "cpu_usage_percent")]
     pub cpu_usage: f32,
+    /// Display string, not part of the schema
+    #[serde(skip)]
     pub cpu_info: String,
+    #[serde(rename = "total_memory_bytes")]
     pub total_memory: u64,
     #[allow(dead_code)]
+    #[serde(rename = "used_memory_bytes")]
     pub used_memory: u64,
+    /// Display string, not part of the schema
+    #[serde(skip)]
     pub memory_info: String,
     pub os_name: String,
     #[allow(dead_code)]
     pub os_version: String,
+    /// Full distribution identity from `/etc/os-release`
+    pub os_release: OsRelease,
     pub desktop_environment: String,
+    #[serde(flatten)]
+    pub session: SessionInfo,
     pub kernel_version: String,
     #[allow(dead_code)]
     pub hostname: String,
+    /// Mounted filesystems, one entry per device
+    pub disks: Vec<DiskInfo>,
+}
+
+impl SystemInfo {
+    /// `/` and `/home` when they are running out of space
+    pub fn low_space_disks(&self) -> Vec<&DiskInfo> {
+        self.disks
+            .iter()
+            .filter(|disk| disk.is_low_on_space())
+            .collect()
+    }
+
+    /// Session line for display, e.g. `Wayland (KWin 6.0.4)`
+    pub fn session_summary(&self) -> String {
+        self.session.summary()
+    }
+}
+
+/// Graphical session details. Detecting them runs `loginctl` and the
+/// compositor or X server, so `session_info` does it once per process.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    pub session_type: SessionType,
+    /// Compositor on Wayland, window manager on X11
+    pub compositor: Option<String>,
+    /// Version of the Wayland compositor or of the X server
+    pub display_server_version: Option<String>,
+}
+
+static SESSION_INFO: std::sync::OnceLock<SessionInfo> = std::sync::OnceLock::new();
+
+/// The graphical session, detected on first use. Blocks on the first call, so
+/// call it from a background thread in the GUI.
+pub fn session_info() -> SessionInfo {
+    SESSION_INFO
+        .get_or_init(|| {
+            let mut sys = System::new();
+            sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+            SessionInfo::detect(&sys)
+        })
+        .clone()
+}
+
+impl SessionInfo {
+    fn detect(sys: &System) -> Self {
+        let session_type = detect_session_type(&SystemRunner, |name| std::env::var(name).ok());
+        let compositor_process = find_compositor(
+            sys.processes()
+                .values()
+                .map(|process| process.name().to_string_lossy().into_owned()),
+            session_type,
+        );
+        Self {
+            session_type,
+            compositor: compositor_process.map(|(_, name)| name.to_string()),
+            display_server_version: display_server_version(
+                &SystemRunner,
+                session_type,
+                compositor_process,
+            ),
+        }
+    }
+
+    /// Session line for display, e.g. `Wayland (KWin 6.0.4)`
+    pub fn summary(&self) -> String {
+        let server = match (self.session_type, &self.display_server_version) {
+            (SessionType::X11, Some(version)) => Some(format!("Xorg {}", version)),
+            _ => None,
+        };
+        let compositor = match (&self.compositor, self.session_type) {
+            (Some(name), SessionType::Wayland) => match &self.display_server_version {
+                Some(version) => Some(format!("{} {}", name, version)),
+                None => Some(name.clone()),
+            },
+            (Some(name), _) => Some(name.clone()),
+            (None, _) => None,
+        };
+
+        let details: Vec<String> = server.into_iter().chain(compositor).collect();
+        if details.is_empty() {
+            self.session_type.to_string()
+        } else {
+            format!("{} ({})", self.session_type, details.join(", "))
+        }
+    }
+}
+
+/// Mount points checked for low free space
+const LOW_SPACE_MOUNT_POINTS: &[&str] = &["/", "/home"];
+/// Free space below which a filesystem counts as low on space
+const LOW_SPACE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const LOW_SPACE_PERCENT: f64 = 10.0;
+
+/// Filesystems that do not hold user data (snap images, RAM disks, ...)
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "squashfs",
+    "overlay",
+    "tmpfs",
+    "devtmpfs",
+    "ramfs",
+    "efivarfs",
+    "fuse.portal",
+];
+
+/// A mounted filesystem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiskInfo {
+    /// Block device, e.g. `/dev/nvme0n1p2`
+    pub device: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub free_bytes: u64,
+}
+
+impl DiskInfo {
+    fn free_percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 100.0;
+        }
+        self.free_bytes as f64 * 100.0 / self.total_bytes as f64
+    }
+
+    /// `/` or `/home` with less than 2 GiB or 10% free
+    pub fn is_low_on_space(&self) -> bool {
+        LOW_SPACE_MOUNT_POINTS.contains(&self.mount_point.as_str())
+            && (self.free_bytes < LOW_SPACE_BYTES || self.free_percent() < LOW_SPACE_PERCENT)
+    }
+
+    /// Display string, e.g. `45.2 GB / 250.0 GB used (ext4, /dev/nvme0n1p2)`
+    pub fn usage_summary(&self) -> String {
+        format!(
+            "{} / {} used ({}, {})",
+            format_size(self.used_bytes),
+            format_size(self.total_bytes),
+            self.file_system,
+            self.device
+        )
+    }
+}
+
+/// Drop pseudo filesystems and repeated mounts of the same device (btrfs
+/// subvolumes, bind mounts), keeping the shortest mount point. Sorted by mount
+/// point.
+fn filter_disks(disks: impl IntoIterator<Item = DiskInfo>) -> Vec<DiskInfo> {
+    let mut disks: Vec<DiskInfo> = disks
+        .into_iter()
+        .filter(|disk| disk.total_bytes > 0)
+        .filter(|disk| !PSEUDO_FILESYSTEMS.contains(&disk.file_system.as_str()))
+        .filter(|disk| !disk.mount_point.starts_with("/snap/"))
+        .collect();
+
+    disks.sort_by(|a, b| {
+        a.device
+            .cmp(&b.device)
+            .then(a.mount_point.len().cmp(&b.mount_point.len()))
+    });
+    disks.dedup_by(|later, first| later.device == first.device);
+    disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    disks
+}
+
+/// Format a size in bytes with GB or TB units
+pub fn format_size(bytes: u64) -> String {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let gb = bytes as f64 / GB;
+    if gb >= 1024.0 {
+        format!("{:.1} TB", gb / 1024.0)
+    } else {
+        format!("{:.1} GB", gb)
+    }
 }
 
+/// Graphical session protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionType {
+    Wayland,
+    X11,
+    Tty,
+    Unknown,
+}
+
+impl SessionType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "wayland" => Some(Self::Wayland),
+            "x11" => Some(Self::X11),
+            "tty" => Some(Self::Tty),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SessionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wayland => write!(f, "Wayland"),
+            Self::X11 => write!(f, "X11"),
+            Self::Tty => write!(f, "TTY"),
+            Self::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Sessions a compositor or window manager process can be running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Serves {
+    Wayland,
+    X11,
+    /// e.g. gnome-shell, which is the compositor of GNOME on Wayland and on Xorg
+    Both,
+}
+
+/// Compositor and window manager processes (process name → display name).
+/// Wayland compositors come first since XWayland sessions may also run an X
+/// window manager.
+const COMPOSITORS: &[(&str, &str, Serves)] = &[
+    ("gnome-shell", "GNOME Shell", Serves::Both),
+    ("kwin_wayland", "KWin", Serves::Wayland),
+    ("sway", "Sway", Serves::Wayland),
+    ("Hyprland", "Hyprland", Serves::Wayland),
+    ("cosmic-comp", "COSMIC", Serves::Wayland),
+    ("niri", "niri", Serves::Wayland),
+    ("river", "river", Serves::Wayland),
+    ("wayfire", "Wayfire", Serves::Wayland),
+    ("labwc", "labwc", Serves::Wayland),
+    ("weston", "Weston", Serves::Wayland),
+    ("kwin_x11", "KWin", Serves::X11),
+    ("mutter", "Mutter", Serves::X11),
+    ("xfwm4", "Xfwm4", Serves::X11),
+    ("muffin", "Muffin", Serves::X11),
+    ("marco", "Marco", Serves::X11),
+    ("openbox", "Openbox", Serves::X11),
+    ("i3", "i3", Serves::X11),
+    ("bspwm", "bspwm", Serves::X11),
+    ("awesome", "awesome", Serves::X11),
+];
+
 pub struct SystemState {
     sys: System,
+    disks: Disks,
 }
 
 impl SystemState {
@@ -29,7 +287,23 @@ impl SystemState {
         let mut sys = System::new_all();
         sys.refresh_all();
 
-        Self { sys }
+        Self {
+            sys,
+            disks: Disks::new_with_refreshed_list(),
+        }
+    }
+
+    /// State for `sample_usage` alone: loads only CPUs and memory, skipping the
+    /// process and disk scans of `new`, so it is cheap enough for the GTK thread
+    pub fn for_usage() -> Self {
+        Self {
+            sys: System::new_with_specifics(
+                RefreshKind::nothing()
+                    .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+                    .with_memory(MemoryRefreshKind::everything()),
+            ),
+            disks: Disks::new(),
+        }
     }
 
     pub fn get_system_info(&self) -> SystemInfo {
@@ -50,14 +324,37 @@ impl SystemState {
         // Format memory info with smart units
         let memory_info = Self::format_memory(used_memory, total_memory);
 
-        let os_name = System::name().unwrap_or_else(|| "Linux".to_string());
-        let os_version = System::os_version().unwrap_or_else(|| "Unknown".to_string());
+        let os_release = OsRelease::load();
+        let os_name = os_release
+            .name
+            .clone()
+            .or_else(System::name)
+            .unwrap_or_else(|| "Linux".to_string());
+        let os_version = os_release
+            .version
+            .clone()
+            .or_else(|| os_release.version_id.clone())
+            .or_else(System::os_version)
+            .unwrap_or_else(|| "Unknown".to_string());
         let kernel_version = System::kernel_version().unwrap_or_else(|| "Unknown".to_string());
         let hostname = System::host_name().unwrap_or_else(|| "localhost".to_string());
 
         // Detect desktop environment with better support for KDE, GNOME, and others
         let desktop_environment = Self::detect_desktop_environment();
 
+        let session = SESSION_INFO
+            .get_or_init(|| SessionInfo::detect(&self.sys))
+            .clone();
+
+        let disks = filter_disks(self.disks.list().iter().map(|disk| DiskInfo {
+            device: disk.name().to_string_lossy().into_owned(),
+            mount_point: disk.mount_point().to_string_lossy().into_owned(),
+            file_system: disk.file_system().to_string_lossy().into_owned(),
+            total_bytes: disk.total_space(),
+            used_bytes: disk.total_space().saturating_sub(disk.available_space()),
+            free_bytes: disk.available_space(),
+        }));
+
         SystemInfo {
             cpu_name,
             cpu_usage,
@@ -67,9 +364,12 @@ impl SystemState {
             memory_info,
             os_name,
             os_version,
+            os_release,
             desktop_environment,
+            session,
             kernel_version,
             hostname,
+            disks,
         }
     }
 
@@ -91,7 +391,7 @@ impl SystemState {
     }
 
     /// Detect the running desktop environment
-    fn detect_desktop_environment() -> String {
+    pub fn detect_desktop_environment() -> String {
         // Desktop environment name mappings (keyword → display name)
         const DE_MAP: &[(&str, &str)] = &[
             ("kde", "KDE Plasma"),
@@ -134,6 +434,67 @@ impl SystemState {
     #[allow(dead_code)]
     pub fn refresh(&mut self) {
         self.sys.refresh_all();
+        self.disks.refresh(true);
+    }
+
+    /// Refresh CPU and memory usage only and return the new readings. CPU
+    /// usage is measured since the previous refresh, so call this on a timer.
+    pub fn sample_usage(&mut self) -> UsageSample {
+        self.sys.refresh_cpu_usage();
+        self.sys.refresh_memory();
+
+        let percent = |used: u64, total: u64| used as f32 * 100.0 / total as f32;
+        UsageSample {
+            cpu_per_core: self.sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect(),
+            memory_percent: percent(self.sys.used_memory(), self.sys.total_memory().max(1)),
+            swap_percent: (self.sys.total_swap() > 0)
+                .then(|| percent(self.sys.used_swap(), self.sys.total_swap())),
+        }
+    }
+}
+
+/// One reading of the live dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageSample {
+    pub cpu_per_core: Vec<f32>,
+    pub memory_percent: f32,
+    /// `None` when the system has no swap
+    pub swap_percent: Option<f32>,
+}
+
+/// The most recent percentages of one dashboard graph, oldest first
+#[derive(Debug, Clone)]
+pub struct UsageHistory {
+    samples: std::collections::VecDeque<f32>,
+    capacity: usize,
+}
+
+impl UsageHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: std::collections::VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add a percentage, dropping the oldest one when full
+    pub fn push(&mut self, percent: f32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(percent.clamp(0.0, 100.0));
+    }
+
+    pub fn samples(&self) -> impl ExactSizeIterator<Item = f32> + '_ {
+        self.samples.iter().copied()
+    }
+
+    pub fn latest(&self) -> Option<f32> {
+        self.samples.back().copied()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
 }
 
@@ -143,9 +504,193 @@ impl Default for SystemState {
     }
 }
 
+/// Session type from `XDG_SESSION_TYPE`, then `WAYLAND_DISPLAY`/`DISPLAY`, then
+/// logind. `env` looks up environment variables.
+fn detect_session_type(
+    runner: &impl CommandRunner,
+    env: impl Fn(&str) -> Option<String>,
+) -> SessionType {
+    if let Some(session_type) = env("XDG_SESSION_TYPE")
+        .as_deref()
+        .and_then(SessionType::from_name)
+    {
+        return session_type;
+    }
+    if env("WAYLAND_DISPLAY").is_some_and(|v| !v.is_empty()) {
+        return SessionType::Wayland;
+    }
+    if env("DISPLAY").is_some_and(|v| !v.is_empty()) {
+        return SessionType::X11;
+    }
+
+    let session = env("XDG_SESSION_ID").unwrap_or_else(|| "auto".to_string());
+    let cmd: Vec<String> = [
+        "loginctl",
+        "show-session",
+        &session,
+        "-p",
+        "Type",
+        "--value",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    runner
+        .run(&cmd)
+        .ok()
+        .filter(|output| output.success())
+        .and_then(|output| SessionType::from_name(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or(SessionType::Unknown)
+}
+
+/// The known compositor or window manager among the running `processes`,
+/// as `(process name, display name)`
+fn find_compositor(
+    processes: impl Iterator<Item = String>,
+    session_type: SessionType,
+) -> Option<(&'static str, &'static str)> {
+    let running: std::collections::HashSet<String> = processes.collect();
+    COMPOSITORS
+        .iter()
+        // An X11 session cannot be using a Wayland-only compositor
+        .filter(|(_, _, serves)| session_type != SessionType::X11 || *serves != Serves::Wayland)
+        .find(|(process, _, _)| running.contains(*process))
+        .map(|(process, name, _)| (*process, *name))
+}
+
+/// Version of the compositor on Wayland, of the X server on X11
+fn display_server_version(
+    runner: &impl CommandRunner,
+    session_type: SessionType,
+    compositor: Option<(&str, &str)>,
+) -> Option<String> {
+    let cmd = match (session_type, compositor) {
+        (SessionType::Wayland, Some((process, _))) => [process, "--version"],
+        (SessionType::X11, _) => ["Xorg", "-version"],
+        _ => return None,
+    };
+    let cmd: Vec<String> = cmd.iter().map(|s| s.to_string()).collect();
+    let output = runner.run(&cmd).ok().filter(|output| output.success())?;
+
+    // Xorg prints its version banner on stderr
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| parse_version(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// First version-looking word of a `--version` banner: `GNOME Shell 46.0` → `46.0`
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .flat_map(str::split_whitespace)
+        .map(|word| word.trim_start_matches('v'))
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+        .map(|word| word.trim_end_matches([',', ')']).to_string())
+}
+
+/// Distribution name and version, e.g. `Ubuntu 24.04`
+pub fn distro_release() -> String {
+    let os_release = OsRelease::load();
+    let name = os_release
+        .name
+        .or_else(System::name)
+        .unwrap_or_else(|| "Linux".to_string());
+    match os_release.version_id.or_else(System::os_version) {
+        Some(version) => format!("{} {}", name, version),
+        None => name,
+    }
+}
+
+/// Whether the modules of the running kernel are gone from `root`, which happens
+/// when the package manager replaced the kernel without keeping the old one
+pub fn running_kernel_modules_missing(root: &Path, kernel_release: &str) -> bool {
+    if kernel_release.is_empty() {
+        return false;
+    }
+    !["usr/lib/modules", "lib/modules"]
+        .iter()
+        .any(|dir| root.join(dir).join(kernel_release).is_dir())
+}
+
+/// Result of scanning `/proc` for services running replaced libraries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutdatedServices {
+    pub services: Vec<String>,
+    /// Every service process could be inspected. Without root, `/proc/PID/maps`
+    /// of processes owned by other users cannot be read, so most system
+    /// services stay unknown.
+    pub complete: bool,
+}
+
+/// systemd services whose processes still map shared libraries that were
+/// deleted or replaced on disk, found by scanning `root/proc`.
+///
+/// This is only a fallback for when the package manager has no such check
+/// (e.g. needrestart).
+pub fn services_using_deleted_libraries(root: &Path) -> OutdatedServices {
+    let Ok(entries) = std::fs::read_dir(root.join("proc")) else {
+        return OutdatedServices::default();
+    };
+
+    let mut outdated = OutdatedServices {
+        services: Vec::new(),
+        complete: true,
+    };
+    for entry in entries.flatten() {
+        let is_pid = entry
+            .file_name()
+            .to_string_lossy()
+            .bytes()
+            .all(|b| b.is_ascii_digit());
+        let service = std::fs::read_to_string(entry.path().join("cgroup"))
+            .ok()
+            .and_then(|cgroup| service_from_cgroup(&cgroup));
+        let (true, Some(service)) = (is_pid, service) else {
+            continue;
+        };
+
+        match std::fs::read_to_string(entry.path().join("maps")) {
+            Ok(maps) if maps.lines().any(maps_deleted_library) => outdated.services.push(service),
+            Ok(_) => {}
+            // The process exited while scanning
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::debug!(
+                    "Cannot inspect {} of {}: {}",
+                    entry.path().display(),
+                    service,
+                    e
+                );
+                outdated.complete = false;
+            }
+        }
+    }
+
+    outdated.services.sort();
+    outdated.services.dedup();
+    outdated
+}
+
+/// `7f3c1a000000-7f3c1a022000 r--p 00000000 08:01 1234 /usr/lib/libssl.so.3 (deleted)`
+fn maps_deleted_library(line: &str) -> bool {
+    line.ends_with(" (deleted)") && line.contains(".so")
+}
+
+/// Extract the system service from `/proc/PID/cgroup`, e.g.
+/// `0::/system.slice/ssh.service`. User sessions are ignored.
+fn service_from_cgroup(cgroup: &str) -> Option<String> {
+    cgroup
+        .lines()
+        .filter_map(|line| line.rsplit(':').next())
+        .filter(|path| path.starts_with("/system.slice/"))
+        .flat_map(|path| path.split('/'))
+        .find(|part| part.ends_with(".service"))
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::FakeRoot;
 
     #[test]
     fn test_format_memory_mb() {
@@ -177,4 +722,189 @@ mod tests {
         assert!(!info.hostname.is_empty());
         assert!(info.total_memory > 0);
     }
+
+    #[test]
+    fn test_session_type_detection_order() {
+        use crate::package_manager::runner::FakeRunner;
+        use std::collections::HashMap;
+
+        let runner = FakeRunner::new().on(
+            &["loginctl", "show-session", "2", "-p", "Type", "--value"],
+            0,
+            "wayland\n",
+            "",
+        );
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            detect_session_type(&runner, |name| vars.get(name).cloned())
+        };
+
+        assert_eq!(
+            detect(&[
+                ("XDG_SESSION_TYPE", "x11"),
+                ("WAYLAND_DISPLAY", "wayland-0")
+            ]),
+            SessionType::X11
+        );
+        assert_eq!(
+            detect(&[("WAYLAND_DISPLAY", "wayland-0")]),
+            SessionType::Wayland
+        );
+        assert_eq!(detect(&[("DISPLAY", ":0")]), SessionType::X11);
+        assert_eq!(detect(&[("XDG_SESSION_ID", "2")]), SessionType::Wayland);
+        assert_eq!(detect(&[]), SessionType::Unknown);
+    }
+
+    #[test]
+    fn test_compositor_and_version() {
+        use crate::package_manager::runner::FakeRunner;
+
+        let processes = ["systemd", "kwin_wayland", "Xwayland", "plasmashell"];
+        let compositor = find_compositor(
+            processes.iter().map(|s| s.to_string()),
+            SessionType::Wayland,
+        );
+        assert_eq!(compositor, Some(("kwin_wayland", "KWin")));
+
+        let runner = FakeRunner::new()
+            .on(&["kwin_wayland", "--version"], 0, "kwin 6.0.4\n", "")
+            .on(
+                &["Xorg", "-version"],
+                0,
+                "",
+                "\nX.Org X Server 1.21.1.11\nX Protocol Version 11, Revision 0\n",
+            );
+        assert_eq!(
+            display_server_version(&runner, SessionType::Wayland, compositor).as_deref(),
+            Some("6.0.4")
+        );
+        assert_eq!(
+            display_server_version(&runner, SessionType::X11, None).as_deref(),
+            Some("1.21.1.11")
+        );
+
+        // GNOME on Xorg runs gnome-shell as its compositor, without a mutter process
+        let processes = ["systemd", "Xorg", "gnome-shell", "gnome-session-binary"];
+        assert_eq!(
+            find_compositor(processes.iter().map(|s| s.to_string()), SessionType::X11),
+            Some(("gnome-shell", "GNOME Shell"))
+        );
+
+        // sway may still be installed on an X11 machine running i3
+        let processes = ["sway", "i3"].iter().map(|s| s.to_string());
+        assert_eq!(
+            find_compositor(processes, SessionType::X11),
+            Some(("i3", "i3"))
+        );
+    }
+
+    fn disk(device: &str, mount_point: &str, fs: &str, total_gb: u64, free_gb: u64) -> DiskInfo {
+        const GB: u64 = 1024 * 1024 * 1024;
+        DiskInfo {
+            device: device.to_string(),
+            mount_point: mount_point.to_string(),
+            file_system: fs.to_string(),
+            total_bytes: total_gb * GB,
+            used_bytes: (total_gb - free_gb) * GB,
+            free_bytes: free_gb * GB,
+        }
+    }
+
+    #[test]
+    fn test_usage_history_keeps_latest_samples() {
+        let mut history = UsageHistory::new(3);
+        assert_eq!(history.latest(), None);
+
+        for percent in [10.0, 20.0, 130.0, -5.0] {
+            history.push(percent);
+        }
+        assert_eq!(history.samples().collect::<Vec<_>>(), [20.0, 100.0, 0.0]);
+        assert_eq!(history.latest(), Some(0.0));
+    }
+
+    #[test]
+    fn test_sample_usage() {
+        let mut state = SystemState::new();
+        let sample = state.sample_usage();
+        assert!(!sample.cpu_per_core.is_empty());
+        assert!(sample.memory_percent > 0.0 && sample.memory_percent <= 100.0);
+    }
+
+    #[test]
+    fn test_filter_disks() {
+        let disks = filter_disks([
+            disk("/dev/nvme0n1p2", "/home", "btrfs", 500, 200),
+            disk("/dev/nvme0n1p2", "/", "btrfs", 500, 200),
+            disk("/dev/nvme0n1p1", "/boot/efi", "vfat", 1, 1),
+            disk("/dev/loop3", "/snap/firefox/3600", "squashfs", 1, 0),
+            disk("tmpfs", "/tmp", "tmpfs", 8, 8),
+        ]);
+
+        let mounts: Vec<&str> = disks.iter().map(|d| d.mount_point.as_str()).collect();
+        assert_eq!(mounts, ["/", "/boot/efi"]);
+    }
+
+    #[test]
+    fn test_low_space_warnings() {
+        // Under 10% free
+        assert!(disk("/dev/sda2", "/", "ext4", 100, 8).is_low_on_space());
+        // Under 2 GiB free on a small partition
+        assert!(disk("/dev/sda3", "/home", "ext4", 15, 1).is_low_on_space());
+        assert!(!disk("/dev/sda2", "/", "ext4", 100, 40).is_low_on_space());
+        // Only / and /home are checked
+        assert!(!disk("/dev/sda1", "/boot/efi", "vfat", 1, 0).is_low_on_space());
+
+        assert_eq!(format_size(512 * 1024 * 1024), "0.5 GB");
+        assert_eq!(format_size(2 * 1024 * 1024 * 1024 * 1024), "2.0 TB");
+    }
+
+    #[test]
+    fn test_services_using_deleted_libraries() {
+        let root = FakeRoot::new("system-proc");
+        let procs = [
+            (
+                "812",
+                "/usr/lib/libssl.so.3 (deleted)",
+                "0::/system.slice/ssh.service\n",
+            ),
+            (
+                "813",
+                "/usr/lib/libc.so.6",
+                "0::/system.slice/cron.service\n",
+            ),
+            (
+                "2201",
+                "/usr/lib/libglib-2.0.so.0 (deleted)",
+                "0::/user.slice/user-1000.slice/session-2.scope\n",
+            ),
+        ];
+        for (pid, lib, cgroup) in procs {
+            let dir = root.join("proc").join(pid);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("maps"),
+                format!(
+                    "7f3c1a000000-7f3c1a022000 r--p 00000000 08:01 1234 {}\n",
+                    lib
+                ),
+            )
+            .unwrap();
+            std::fs::write(dir.join("cgroup"), cgroup).unwrap();
+        }
+
+        let outdated = services_using_deleted_libraries(&root);
+        assert_eq!(outdated.services, ["ssh.service"]);
+        assert!(outdated.complete);
+
+        // An unreadable maps file leaves the result unknown rather than clean
+        let dir = root.join("proc/900");
+        std::fs::create_dir_all(dir.join("maps")).unwrap();
+        std::fs::write(dir.join("cgroup"), "0::/system.slice/dbus.service\n").unwrap();
+        let outdated = services_using_deleted_libraries(&root);
+        assert_eq!(outdated.services, ["ssh.service"]);
+        assert!(!outdated.complete);
+    }
 }
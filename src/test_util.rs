@@ -0,0 +1,32 @@
+//! Helpers shared by the unit tests
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Empty scratch directory acting as the filesystem root, removed when dropped
+pub struct FakeRoot(PathBuf);
+
+impl FakeRoot {
+    /// Create `ro-start-<name>-<pid>` in the temp directory, replacing any
+    /// leftover from an earlier run
+    pub fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("ro-start-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        Self(root)
+    }
+}
+
+impl Deref for FakeRoot {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for FakeRoot {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::boot::{BootAnalysis, BootHistory};
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+
+/// Build the Boot page: how long each boot phase took, the slowest units and
+/// the boot times of previous boots. Opening the page records the current
+/// boot in the history.
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let header = GtkBox::new(Orientation::Horizontal, 12);
+    let title_label = Label::new(Some(&t.sidebar.boot));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    title_label.set_hexpand(true);
+    header.append(&title_label);
+
+    let refresh_button = Button::from_icon_name("view-refresh-symbolic");
+    refresh_button.set_tooltip_text(Some(&t.boot.refresh));
+    refresh_button.set_valign(gtk::Align::Center);
+    header.append(&refresh_button);
+    content.append(&header);
+
+    // Holds the groups, replaced on every refresh
+    let results_box = GtkBox::new(Orientation::Vertical, 24);
+    content.append(&results_box);
+
+    refresh_button.connect_clicked({
+        let results_box = results_box.clone();
+        move |_| load(&results_box)
+    });
+
+    let loaded = std::cell::Cell::new(false);
+    content.connect_map(move |_| {
+        if !loaded.replace(true) {
+            load(&results_box);
+        }
+    });
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+fn load(results_box: &GtkBox) {
+    let t = crate::i18n::t();
+    let group = adw::PreferencesGroup::new();
+    group.set_description(Some(&t.boot.analyzing));
+    clear(results_box);
+    results_box.append(&group);
+
+    let handle = crate::runtime::spawn_blocking(|| {
+        let analysis = crate::boot::analyze();
+        let history = match &analysis {
+            Ok(analysis) => crate::boot::record_boot(&analysis.times),
+            Err(_) => BootHistory::path().and_then(|path| BootHistory::load_from(&path)),
+        };
+        let history = history.unwrap_or_else(|e| {
+            tracing::warn!("Boot history unavailable: {}", e);
+            BootHistory::default()
+        });
+        (analysis, history)
+    });
+
+    let results_box = results_box.clone();
+    glib::spawn_future_local(async move {
+        match handle.await {
+            Ok((analysis, history)) => {
+                clear(&results_box);
+                match analysis {
+                    Ok(analysis) => show_analysis(&results_box, &analysis, &history),
+                    Err(e) => {
+                        tracing::warn!("Boot analysis failed: {}", e);
+                        let group = adw::PreferencesGroup::new();
+                        group.set_title(&t.boot.this_boot);
+                        group.set_description(Some(&t.boot.unavailable));
+                        results_box.append(&group);
+                    }
+                }
+                if !history.boots.is_empty() {
+                    results_box.append(&history_group(&history));
+                }
+            }
+            Err(e) => tracing::error!("Boot analysis task failed: {}", e),
+        }
+    });
+}
+
+fn clear(results_box: &GtkBox) {
+    while let Some(child) = results_box.first_child() {
+        results_box.remove(&child);
+    }
+}
+
+fn show_analysis(results_box: &GtkBox, analysis: &BootAnalysis, history: &BootHistory) {
+    let t = crate::i18n::t();
+    let times_group = adw::PreferencesGroup::new();
+    times_group.set_title(&t.boot.this_boot);
+
+    if let Some(regression) = history.regression() {
+        let row = adw::ActionRow::new();
+        row.set_title(&t.boot.regression.replace(
+            "{time}",
+            &crate::boot::format_ms(regression.latest_ms - regression.usual_ms),
+        ));
+        let usual = crate::boot::format_ms(regression.usual_ms);
+        row.set_subtitle(&match &regression.previous_kernel {
+            Some(kernel) => t
+                .boot
+                .usually_kernel_changed
+                .replace("{time}", &usual)
+                .replace("{kernel}", kernel),
+            None => t.boot.usually.replace("{time}", &usual),
+        });
+        let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+        icon.add_css_class("warning");
+        row.add_prefix(&icon);
+        times_group.add(&row);
+    }
+
+    for (label, ms) in analysis.times.phases() {
+        let row = adw::ActionRow::new();
+        row.set_title(label);
+        row.set_subtitle(&crate::boot::format_ms(ms));
+        times_group.add(&row);
+    }
+
+    let total_row = adw::ActionRow::new();
+    total_row.set_title(&t.boot.total);
+    total_row.set_subtitle(&crate::boot::format_ms(analysis.times.total_ms));
+    total_row.add_prefix(&gtk::Image::from_icon_name(
+        "preferences-system-time-symbolic",
+    ));
+    times_group.add(&total_row);
+    results_box.append(&times_group);
+
+    if !analysis.slowest_units.is_empty() {
+        let units_group = adw::PreferencesGroup::new();
+        units_group.set_title(&t.boot.slowest_units);
+        units_group.set_description(Some(&t.boot.slowest_units_description));
+        for unit in &analysis.slowest_units {
+            let row = adw::ActionRow::new();
+            row.set_title(&glib::markup_escape_text(&unit.unit));
+            row.set_subtitle(&crate::boot::format_ms(unit.time_ms));
+            units_group.add(&row);
+        }
+        results_box.append(&units_group);
+    }
+}
+
+fn history_group(history: &BootHistory) -> adw::PreferencesGroup {
+    let t = crate::i18n::t();
+    let group = adw::PreferencesGroup::new();
+    group.set_title(&t.boot.history);
+
+    for boot in history.boots.iter().rev() {
+        let date = glib::DateTime::from_unix_local(boot.booted_at as i64)
+            .and_then(|date| date.format("%x %H:%M"))
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+
+        let row = adw::ActionRow::new();
+        row.set_title(&date);
+        row.set_subtitle(
+            &t.boot
+                .history_entry
+                .replace("{time}", &crate::boot::format_ms(boot.times.total_ms))
+                .replace("{kernel}", &boot.kernel),
+        );
+        group.add(&row);
+    }
+    group
+}
@@ -0,0 +1,209 @@
+use crate::system::{SystemState, UsageHistory};
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Label, Orientation};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Time between two readings
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// Readings kept per graph: five minutes
+const HISTORY_LENGTH: usize = 150;
+
+/// RGB colors of the graphs
+const CPU_COLOR: (f64, f64, f64) = (0.21, 0.52, 0.89);
+const MEMORY_COLOR: (f64, f64, f64) = (0.18, 0.76, 0.49);
+const SWAP_COLOR: (f64, f64, f64) = (0.90, 0.38, 0.0);
+
+/// A row with the current percentage and a graph of its history
+struct Graph {
+    row: adw::ActionRow,
+    area: gtk::DrawingArea,
+    history: Rc<RefCell<UsageHistory>>,
+}
+
+impl Graph {
+    fn new(title: &str, color: (f64, f64, f64)) -> Self {
+        let history = Rc::new(RefCell::new(UsageHistory::new(HISTORY_LENGTH)));
+
+        let area = gtk::DrawingArea::new();
+        area.set_content_width(240);
+        area.set_content_height(36);
+        area.set_valign(gtk::Align::Center);
+        area.set_draw_func({
+            let history = history.clone();
+            move |_, cr, width, height| {
+                draw_sparkline(cr, &history.borrow(), width, height, color);
+            }
+        });
+
+        let row = adw::ActionRow::new();
+        row.set_title(title);
+        row.set_subtitle("—");
+        row.add_suffix(&area);
+
+        Self { row, area, history }
+    }
+
+    fn push(&self, percent: f32) {
+        let mut history = self.history.borrow_mut();
+        history.push(percent);
+        if let Some(latest) = history.latest() {
+            self.row.set_subtitle(&format!("{:.0}%", latest));
+        }
+        drop(history);
+        self.area.queue_draw();
+    }
+}
+
+/// Graphs filled by the refresh timer; created on first show
+struct Dashboard {
+    state: SystemState,
+    cores: Vec<Graph>,
+    memory: Graph,
+    swap: Graph,
+    /// Swap subtitle when the system has none
+    no_swap: String,
+}
+
+impl Dashboard {
+    fn refresh(&mut self) {
+        let sample = self.state.sample_usage();
+        for (graph, usage) in self.cores.iter().zip(&sample.cpu_per_core) {
+            graph.push(*usage);
+        }
+        self.memory.push(sample.memory_percent);
+        match sample.swap_percent {
+            Some(percent) => self.swap.push(percent),
+            None => self.swap.row.set_subtitle(&self.no_swap),
+        }
+    }
+}
+
+/// Build the Dashboard page: per-core CPU, memory and swap usage over the last
+/// few minutes. Readings are only taken while the page is visible.
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let title_label = Label::new(Some(&t.sidebar.dashboard));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    content.append(&title_label);
+
+    let cpu_group = adw::PreferencesGroup::new();
+    cpu_group.set_title(&t.dashboard.cpu);
+    content.append(&cpu_group);
+
+    let memory_group = adw::PreferencesGroup::new();
+    memory_group.set_title(&t.dashboard.memory);
+    content.append(&memory_group);
+
+    let dashboard: Rc<RefCell<Option<Dashboard>>> = Rc::new(RefCell::new(None));
+    let timer: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+    content.connect_map({
+        let dashboard = dashboard.clone();
+        let timer = timer.clone();
+        move |_| {
+            if dashboard.borrow().is_none() {
+                let mut state = SystemState::for_usage();
+                let cores: Vec<Graph> = (0..state.sample_usage().cpu_per_core.len())
+                    .map(|core| {
+                        Graph::new(
+                            &t.dashboard.core.replace("{core}", &core.to_string()),
+                            CPU_COLOR,
+                        )
+                    })
+                    .collect();
+                for graph in &cores {
+                    cpu_group.add(&graph.row);
+                }
+                let memory = Graph::new(&t.dashboard.memory, MEMORY_COLOR);
+                let swap = Graph::new(&t.dashboard.swap, SWAP_COLOR);
+                memory_group.add(&memory.row);
+                memory_group.add(&swap.row);
+
+                *dashboard.borrow_mut() = Some(Dashboard {
+                    state,
+                    cores,
+                    memory,
+                    swap,
+                    no_swap: t.dashboard.no_swap.clone(),
+                });
+            }
+
+            tracing::debug!("Dashboard refresh started");
+            let dashboard = dashboard.clone();
+            let source = glib::timeout_add_local(REFRESH_INTERVAL, move || {
+                if let Some(dashboard) = dashboard.borrow_mut().as_mut() {
+                    dashboard.refresh();
+                }
+                glib::ControlFlow::Continue
+            });
+            if let Some(previous) = timer.replace(Some(source)) {
+                previous.remove();
+            }
+        }
+    });
+
+    // Unmapped when another page is selected or the window is hidden
+    content.connect_unmap(move |_| {
+        if let Some(source) = timer.take() {
+            tracing::debug!("Dashboard refresh paused");
+            source.remove();
+        }
+    });
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+/// Draw `history` as a filled line graph scaled to 0–100%, newest on the right
+fn draw_sparkline(
+    cr: &gtk::cairo::Context,
+    history: &UsageHistory,
+    width: i32,
+    height: i32,
+    (r, g, b): (f64, f64, f64),
+) {
+    let (width, height) = (f64::from(width), f64::from(height));
+    let step = width / (history.capacity().max(2) - 1) as f64;
+    let samples = history.samples();
+    let start = width - step * (samples.len().max(1) - 1) as f64;
+
+    let points: Vec<(f64, f64)> = samples
+        .enumerate()
+        .map(|(i, percent)| {
+            (
+                start + step * i as f64,
+                height - f64::from(percent) / 100.0 * height,
+            )
+        })
+        .collect();
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return;
+    };
+
+    cr.move_to(first.0, first.1);
+    for (x, y) in &points[1..] {
+        cr.line_to(*x, *y);
+    }
+    cr.set_source_rgb(r, g, b);
+    cr.set_line_width(1.5);
+    let _ = cr.stroke_preserve();
+
+    cr.line_to(last.0, height);
+    cr.line_to(first.0, height);
+    cr.close_path();
+    cr.set_source_rgba(r, g, b, 0.25);
+    let _ = cr.fill();
+}
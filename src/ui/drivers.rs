@@ -0,0 +1,181 @@
+use crate::drivers::{DriverSuggestion, Gpu};
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+
+/// Build the Drivers page: detected GPUs, their drivers and the recommended
+/// proprietary driver. Hardware is scanned when the page is first shown.
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let header = GtkBox::new(Orientation::Vertical, 12);
+    let title_label = Label::new(Some(&t.drivers.title));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    let desc_label = Label::new(Some(&t.drivers.description));
+    desc_label.add_css_class("dim-label");
+    desc_label.set_wrap(true);
+    desc_label.set_xalign(0.0);
+    header.append(&title_label);
+    header.append(&desc_label);
+    content.append(&header);
+
+    let gpu_group = adw::PreferencesGroup::new();
+    gpu_group.set_title(&t.drivers.detected);
+    gpu_group.set_description(Some(&t.drivers.detecting));
+    content.append(&gpu_group);
+
+    let suggestion_group = adw::PreferencesGroup::new();
+    suggestion_group.set_visible(false);
+    content.append(&suggestion_group);
+
+    let group = adw::PreferencesGroup::new();
+
+    let session_row = adw::ActionRow::new();
+    session_row.set_title(&t.drivers.session_type);
+    session_row.add_prefix(&gtk::Image::from_icon_name("video-display-symbolic"));
+    group.add(&session_row);
+
+    content.append(&group);
+
+    let scanned = std::cell::Cell::new(false);
+    content.connect_map(move |_| {
+        if scanned.replace(true) {
+            return;
+        }
+        let handle = crate::runtime::spawn_blocking(|| {
+            let gpus = crate::drivers::detect_gpus();
+            let suggestion = crate::drivers::recommend_driver(&gpus);
+            (gpus, suggestion, crate::system::session_info().summary())
+        });
+
+        let gpu_group = gpu_group.clone();
+        let suggestion_group = suggestion_group.clone();
+        let session_row = session_row.clone();
+        glib::spawn_future_local(async move {
+            let (gpus, suggestion, session) = handle.await.unwrap_or_default();
+            session_row.set_subtitle(&session);
+            show_gpus(&gpu_group, &gpus);
+            if let Some(suggestion) = suggestion {
+                show_suggestion(&suggestion_group, suggestion);
+            }
+        });
+    });
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+fn show_gpus(group: &adw::PreferencesGroup, gpus: &[Gpu]) {
+    let t = crate::i18n::t();
+
+    if gpus.is_empty() {
+        group.set_description(Some(&t.drivers.unknown_gpu));
+        return;
+    }
+    group.set_description(None);
+
+    for gpu in gpus {
+        tracing::info!(
+            "Detected GPU {}: {} ({:?})",
+            gpu.address,
+            gpu.name(),
+            gpu.driver
+        );
+
+        let row = adw::ActionRow::new();
+        row.set_title(&glib::markup_escape_text(&gpu.name()));
+        row.set_subtitle(&match &gpu.driver {
+            Some(driver) => format!(
+                "{} {} ({})",
+                t.drivers.driver_installed, driver, t.drivers.driver_current
+            ),
+            None => t.drivers.driver_not_found.clone(),
+        });
+        row.add_prefix(&gtk::Image::from_icon_name("video-display-symbolic"));
+
+        if gpu.driver.as_deref() == Some("nvidia") {
+            let launch_button = Button::with_label(&t.drivers.btn_launch);
+            launch_button.set_valign(gtk::Align::Center);
+            launch_button.connect_clicked(|_| {
+                if let Err(e) = std::process::Command::new("nvidia-settings").spawn() {
+                    tracing::error!("Failed to open nvidia-settings: {}", e);
+                    crate::notifications::notify_error(&crate::i18n::t().drivers.settings_failed);
+                }
+            });
+            row.add_suffix(&launch_button);
+        }
+
+        group.add(&row);
+    }
+}
+
+fn show_suggestion(group: &adw::PreferencesGroup, suggestion: DriverSuggestion) {
+    let t = crate::i18n::t();
+
+    let row = adw::ActionRow::new();
+    row.set_title(&t.drivers.proprietary_driver);
+    let recommended = t
+        .drivers
+        .recommended_via
+        .replace("{tool}", &suggestion.tool.to_string());
+    row.set_subtitle(&match &suggestion.note {
+        Some(note) => format!("{}. {}", recommended, note),
+        None => recommended,
+    });
+    row.add_prefix(&gtk::Image::from_icon_name(
+        "system-software-install-symbolic",
+    ));
+
+    let install_button = Button::with_label(&t.software.btn_install);
+    install_button.set_valign(gtk::Align::Center);
+    install_button.add_css_class("suggested-action");
+    // The install cannot succeed until the user has taken the noted step
+    if let Some(note) = &suggestion.note {
+        install_button.set_sensitive(false);
+        install_button.set_tooltip_text(Some(note));
+    }
+    install_button.connect_clicked(move |button| {
+        button.set_sensitive(false);
+
+        let suggestion = suggestion.clone();
+        let handle = crate::runtime::spawn_blocking(move || {
+            crate::drivers::install_driver(&suggestion, |line| tracing::info!("{}", line))
+        });
+
+        let button = button.clone();
+        let t = crate::i18n::t();
+        glib::spawn_future_local(async move {
+            match handle.await {
+                Ok(Ok(0)) => {
+                    crate::notifications::notify_success(&t.drivers.install_success);
+                    let _ = button.activate_action("win.refresh-restart-status", None);
+                }
+                Ok(Ok(code)) => {
+                    tracing::error!("Driver install exited with code {}", code);
+                    crate::notifications::notify_error(&t.drivers.install_failed);
+                    button.set_sensitive(true);
+                }
+                Ok(Err(e)) => {
+                    tracing::error!("Driver install failed: {}", e);
+                    crate::notifications::notify_error(&t.drivers.install_failed);
+                    button.set_sensitive(true);
+                }
+                Err(e) => tracing::error!("Driver install task failed: {}", e),
+            }
+        });
+    });
+    row.add_suffix(&install_button);
+
+    group.add(&row);
+    group.set_visible(true);
+}
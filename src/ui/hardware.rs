@@ -0,0 +1,172 @@
+use crate::hardware::HardwareInfo;
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Build the Hardware page: computer model, firmware, batteries and connected
+/// devices, with a button to copy it all for a support request. Hardware is
+/// scanned when the page is first shown.
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let header = GtkBox::new(Orientation::Horizontal, 12);
+    let title_label = Label::new(Some(&t.sidebar.hardware));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    title_label.set_hexpand(true);
+    header.append(&title_label);
+
+    let copy_button = Button::from_icon_name("edit-copy-symbolic");
+    copy_button.set_tooltip_text(Some(&t.hardware.copy));
+    copy_button.set_valign(gtk::Align::Center);
+    copy_button.set_sensitive(false);
+    header.append(&copy_button);
+    content.append(&header);
+
+    let groups = GtkBox::new(Orientation::Vertical, 24);
+    content.append(&groups);
+
+    let info: Rc<RefCell<Option<HardwareInfo>>> = Rc::new(RefCell::new(None));
+
+    copy_button.connect_clicked({
+        let info = info.clone();
+        let copied = t.hardware.copied.clone();
+        move |button| {
+            if let Some(info) = info.borrow().as_ref() {
+                button.clipboard().set_text(&info.to_text());
+                button.set_tooltip_text(Some(&copied));
+            }
+        }
+    });
+
+    let scanned = std::cell::Cell::new(false);
+    content.connect_map(move |_| {
+        if scanned.replace(true) {
+            return;
+        }
+        let handle = crate::runtime::spawn_blocking(HardwareInfo::detect);
+
+        let groups = groups.clone();
+        let copy_button = copy_button.clone();
+        let info = info.clone();
+        glib::spawn_future_local(async move {
+            let hardware = handle.await.unwrap_or_default();
+            show_hardware(&groups, &hardware);
+            *info.borrow_mut() = Some(hardware);
+            copy_button.set_sensitive(true);
+        });
+    });
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+fn show_hardware(container: &GtkBox, info: &HardwareInfo) {
+    let t = crate::i18n::t();
+    let system_group = adw::PreferencesGroup::new();
+    system_group.set_title(&t.hardware.computer);
+    let dmi = &info.dmi;
+    for (title, value, icon) in [
+        (&t.hardware.model, dmi.model(), "computer-symbolic"),
+        (
+            &t.hardware.chassis,
+            dmi.chassis.clone(),
+            "computer-symbolic",
+        ),
+        (
+            &t.hardware.motherboard,
+            dmi.board(),
+            "application-x-firmware-symbolic",
+        ),
+        (
+            &t.hardware.bios,
+            dmi.bios(),
+            "application-x-firmware-symbolic",
+        ),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        system_group.add(&info_row(title, &value, icon));
+    }
+    container.append(&system_group);
+
+    if !info.batteries.is_empty() || info.on_ac_power.is_some() {
+        let power_group = adw::PreferencesGroup::new();
+        power_group.set_title(&t.hardware.power);
+        for battery in &info.batteries {
+            let title = [&battery.manufacturer, &battery.model]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let title = if title.is_empty() {
+                battery.name.clone()
+            } else {
+                title
+            };
+            power_group.add(&info_row(&title, &battery.summary(), "battery-symbolic"));
+        }
+        if let Some(online) = info.on_ac_power {
+            power_group.add(&info_row(
+                &t.hardware.ac_adapter,
+                if online {
+                    &t.hardware.connected
+                } else {
+                    &t.hardware.disconnected
+                },
+                "ac-adapter-symbolic",
+            ));
+        }
+        container.append(&power_group);
+    }
+
+    let usb_group = adw::PreferencesGroup::new();
+    usb_group.set_title(&t.hardware.usb_devices);
+    if info.usb_devices.is_empty() {
+        usb_group.set_description(Some(&t.hardware.no_usb));
+    }
+    for device in &info.usb_devices {
+        usb_group.add(&info_row(
+            &device.name(),
+            &format!("{:04x}:{:04x}", device.vendor_id, device.product_id),
+            "media-removable-symbolic",
+        ));
+    }
+    container.append(&usb_group);
+
+    let pci_group = adw::PreferencesGroup::new();
+    pci_group.set_title(&t.hardware.pci_devices);
+    for device in &info.pci_devices {
+        let subtitle = match &device.driver {
+            Some(driver) => format!("{} · {} · {}", device.class_name(), device.address, driver),
+            None => format!("{} · {}", device.class_name(), device.address),
+        };
+        pci_group.add(&info_row(
+            &device.name(),
+            &subtitle,
+            "application-x-firmware-symbolic",
+        ));
+    }
+    container.append(&pci_group);
+}
+
+fn info_row(title: &str, subtitle: &str, icon_name: &str) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    row.set_title(&glib::markup_escape_text(title));
+    row.set_subtitle(&glib::markup_escape_text(subtitle));
+    row.set_subtitle_selectable(true);
+    row.add_prefix(&gtk::Image::from_icon_name(icon_name));
+    row
+}
@@ -0,0 +1,168 @@
+use crate::doctor::{CheckResult, Fix, Severity};
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+
+/// Build the Health page: the result of every health check, with a button for
+/// the checks that have a fix. Checks run when the page is first shown and
+/// again after a fix or a click on "Check Again".
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let header = GtkBox::new(Orientation::Horizontal, 12);
+    let title_label = Label::new(Some(&t.sidebar.health));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    title_label.set_hexpand(true);
+    header.append(&title_label);
+
+    let refresh_button = Button::from_icon_name("view-refresh-symbolic");
+    refresh_button.set_tooltip_text(Some(&t.health.check_again));
+    refresh_button.set_valign(gtk::Align::Center);
+    header.append(&refresh_button);
+    content.append(&header);
+
+    // Holds the results group, replaced on every run
+    let results_box = GtkBox::new(Orientation::Vertical, 0);
+    content.append(&results_box);
+
+    refresh_button.connect_clicked({
+        let results_box = results_box.clone();
+        move |_| run_checks(&results_box)
+    });
+
+    let checked = std::cell::Cell::new(false);
+    content.connect_map(move |_| {
+        if !checked.replace(true) {
+            run_checks(&results_box);
+        }
+    });
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+fn run_checks(results_box: &GtkBox) {
+    let group = adw::PreferencesGroup::new();
+    group.set_description(Some(&crate::i18n::t().health.checking));
+    replace_results(results_box, &group);
+
+    let handle = crate::runtime::spawn_blocking(crate::doctor::run_checks);
+
+    let results_box = results_box.clone();
+    glib::spawn_future_local(async move {
+        let results = handle.await.unwrap_or_default();
+        show_results(&results_box, &results);
+    });
+}
+
+fn replace_results(results_box: &GtkBox, group: &adw::PreferencesGroup) {
+    while let Some(child) = results_box.first_child() {
+        results_box.remove(&child);
+    }
+    results_box.append(group);
+}
+
+fn show_results(results_box: &GtkBox, results: &[CheckResult]) {
+    let t = crate::i18n::t();
+    let group = adw::PreferencesGroup::new();
+    group.set_description(Some(match crate::doctor::overall_severity(results) {
+        Severity::Ok | Severity::Info => &t.health.no_problems,
+        Severity::Warning => &t.health.attention,
+        Severity::Critical => &t.health.critical,
+    }));
+
+    for result in results {
+        tracing::debug!("Health check {:?}: {:?}", result.check, result.severity);
+
+        let row = adw::ExpanderRow::new();
+        row.set_title(result.check.title());
+        row.set_subtitle(&glib::markup_escape_text(&result.summary));
+        row.set_enable_expansion(!result.details.is_empty());
+        row.set_show_enable_switch(false);
+
+        let icon = gtk::Image::from_icon_name(result.severity.icon_name());
+        match result.severity {
+            Severity::Ok => icon.add_css_class("success"),
+            Severity::Warning => icon.add_css_class("warning"),
+            Severity::Critical => icon.add_css_class("error"),
+            Severity::Info => {}
+        }
+        row.add_prefix(&icon);
+
+        for detail in &result.details {
+            let detail_row = adw::ActionRow::new();
+            detail_row.set_title(&glib::markup_escape_text(detail));
+            detail_row.set_title_selectable(true);
+            row.add_row(&detail_row);
+        }
+
+        if let Some(fix) = &result.fix {
+            row.add_suffix(&fix_button(results_box, fix.clone()));
+        }
+        group.add(&row);
+    }
+
+    replace_results(results_box, &group);
+}
+
+fn fix_button(results_box: &GtkBox, fix: Fix) -> Button {
+    let button = Button::with_label(&fix.label);
+    button.set_valign(gtk::Align::Center);
+    button.add_css_class("suggested-action");
+
+    let results_box = results_box.clone();
+    button.connect_clicked(move |button| match fix.confirmation.clone() {
+        Some(confirmation) => {
+            let parent = button.root().and_downcast::<gtk::Window>();
+            let button = button.clone();
+            let results_box = results_box.clone();
+            let fix = fix.clone();
+            let label = fix.label.clone();
+            crate::ui::dialogs::show_confirm(
+                parent.as_ref(),
+                &format!("{}?", label),
+                &confirmation,
+                &label,
+                Box::new(move || apply_fix(&button, &results_box, fix.clone())),
+            );
+        }
+        None => apply_fix(button, &results_box, fix.clone()),
+    });
+    button
+}
+
+fn apply_fix(button: &Button, results_box: &GtkBox, fix: Fix) {
+    button.set_sensitive(false);
+
+    let handle = crate::runtime::spawn_blocking(move || {
+        crate::doctor::apply_fix(&fix, |line| tracing::info!("{}", line))
+    });
+
+    let button = button.clone();
+    let results_box = results_box.clone();
+    glib::spawn_future_local(async move {
+        match handle.await {
+            Ok(Ok(0)) => run_checks(&results_box),
+            Ok(Ok(code)) => {
+                tracing::error!("Fix exited with code {}", code);
+                crate::notifications::notify_error(&crate::i18n::t().health.fix_failed);
+                button.set_sensitive(true);
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Fix failed: {}", e);
+                crate::notifications::notify_error(&crate::i18n::t().health.fix_failed);
+                button.set_sensitive(true);
+            }
+            Err(e) => tracing::error!("Fix task failed: {}", e),
+        }
+    });
+}
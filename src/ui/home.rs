@@ -0,0 +1,320 @@
+use crate::os_release::OsRelease;
+use crate::system::{SystemInfo, SystemState};
+use crate::ui::Page;
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+
+/// Ro-Start source code
+const PROJECT_URL: &str = "https://github.com/Acik-Kaynak-Gelistirme-Toplulugu/ro-start";
+
+/// Build the Home page: welcome text, system information and quick actions
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 24);
+    main_box.set_margin_top(24);
+    main_box.set_margin_bottom(24);
+    main_box.set_margin_start(24);
+    main_box.set_margin_end(24);
+
+    let os_release = OsRelease::load();
+
+    // Welcome section, branded with the distribution logo
+    let welcome_box = GtkBox::new(Orientation::Vertical, 12);
+    let logo = gtk::Image::from_icon_name(os_release.logo.as_deref().unwrap_or("ro-start"));
+    logo.set_pixel_size(96);
+    welcome_box.append(&logo);
+    let welcome_label = Label::new(Some(&t.home.title));
+    welcome_label.add_css_class("title-1");
+    let desc_label = Label::new(Some(&t.home.description));
+    desc_label.add_css_class("dim-label");
+    welcome_box.append(&welcome_label);
+    welcome_box.append(&desc_label);
+    main_box.append(&welcome_box);
+
+    // System info card
+    let sys_card = create_system_info_card();
+    main_box.append(&sys_card);
+
+    // Quick actions card
+    let actions_card = create_actions_card();
+    main_box.append(&actions_card);
+
+    // Useful links card
+    let links = links(&t, &os_release);
+    if !links.is_empty() {
+        main_box.append(&create_links_card(&t, links));
+    }
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&main_box));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+fn create_system_info_card() -> libadwaita::PreferencesGroup {
+    let group = libadwaita::PreferencesGroup::new();
+
+    group.set_title("System Information");
+
+    // Reading the system and detecting the session runs commands, so do it off
+    // the main thread
+    let handle = crate::runtime::spawn_blocking(|| SystemState::new().get_system_info());
+    let card = group.clone();
+    glib::spawn_future_local(async move {
+        match handle.await {
+            Ok(info) => add_system_info_rows(&card, &info),
+            Err(e) => tracing::error!("System information task failed: {}", e),
+        }
+    });
+
+    group
+}
+
+fn add_system_info_rows(group: &libadwaita::PreferencesGroup, info: &SystemInfo) {
+    // OS Row
+    let os_row = libadwaita::ActionRow::new();
+    os_row.set_title("Operating System");
+    os_row.set_subtitle(
+        info.os_release
+            .pretty_name
+            .as_deref()
+            .unwrap_or(&info.os_name),
+    );
+    os_row.add_prefix(&gtk::Image::from_icon_name("computer-symbolic"));
+    group.add(&os_row);
+
+    // Desktop Row
+    let desktop_row = libadwaita::ActionRow::new();
+    desktop_row.set_title("Desktop Environment");
+    desktop_row.set_subtitle(&info.desktop_environment);
+    desktop_row.add_prefix(&gtk::Image::from_icon_name("video-display-symbolic"));
+    group.add(&desktop_row);
+
+    // Session Row
+    let session_row = libadwaita::ActionRow::new();
+    session_row.set_title("Session");
+    session_row.set_subtitle(&info.session_summary());
+    session_row.add_prefix(&gtk::Image::from_icon_name(
+        "preferences-desktop-display-symbolic",
+    ));
+    group.add(&session_row);
+
+    // Kernel Row
+    let kernel_row = libadwaita::ActionRow::new();
+    kernel_row.set_title("Kernel");
+    kernel_row.set_subtitle(&info.kernel_version);
+    kernel_row.add_prefix(&gtk::Image::from_icon_name("utilities-terminal-symbolic"));
+    group.add(&kernel_row);
+
+    // Memory Row
+    let memory_row = libadwaita::ActionRow::new();
+    memory_row.set_title("Memory");
+    memory_row.set_subtitle(&info.memory_info);
+    memory_row.add_prefix(&gtk::Image::from_icon_name("drive-harddisk-symbolic"));
+    group.add(&memory_row);
+
+    // Storage Row, expanded to one row per filesystem
+    let storage_row = libadwaita::ExpanderRow::new();
+    storage_row.set_title("Storage");
+    storage_row.add_prefix(&gtk::Image::from_icon_name(
+        "drive-harddisk-system-symbolic",
+    ));
+    if let Some(root) = info.disks.iter().find(|disk| disk.mount_point == "/") {
+        storage_row.set_subtitle(&root.usage_summary());
+    }
+    for disk in &info.disks {
+        let disk_row = libadwaita::ActionRow::new();
+        disk_row.set_title(&disk.mount_point);
+        disk_row.set_subtitle(&disk.usage_summary());
+        storage_row.add_row(&disk_row);
+    }
+    let low_space = info.low_space_disks();
+    if !low_space.is_empty() {
+        let mount_points: Vec<&str> = low_space.iter().map(|d| d.mount_point.as_str()).collect();
+        tracing::warn!("Low disk space on {}", mount_points.join(", "));
+        storage_row.set_subtitle(&format!(
+            "Running out of space on {}",
+            mount_points.join(" and ")
+        ));
+        storage_row.add_css_class("warning");
+        storage_row.add_suffix(&gtk::Image::from_icon_name("dialog-warning-symbolic"));
+    }
+    group.add(&storage_row);
+
+    // CPU Row
+    let cpu_row = libadwaita::ActionRow::new();
+    cpu_row.set_title("CPU");
+    // Live usage is on the dashboard page
+    cpu_row.set_subtitle(&info.cpu_name);
+    cpu_row.add_prefix(&gtk::Image::from_icon_name("cpu-symbolic"));
+    group.add(&cpu_row);
+}
+
+fn create_actions_card() -> libadwaita::PreferencesGroup {
+    let group = libadwaita::PreferencesGroup::new();
+    group.set_title("Quick Actions");
+
+    // Detect DE once and share across callbacks
+    let de = std::rc::Rc::new(SystemState::detect_desktop_environment());
+
+    // --- Update System ---
+    let update_row = libadwaita::ActionRow::new();
+    update_row.set_title("Update System");
+    update_row.set_subtitle("Check and install available updates");
+    update_row.add_prefix(&gtk::Image::from_icon_name(
+        "software-update-available-symbolic",
+    ));
+    update_row.set_activatable(true);
+
+    let update_button = Button::with_label("Update");
+    update_button.set_valign(gtk::Align::Center);
+    update_button.add_css_class("suggested-action");
+    update_row.add_suffix(&update_button);
+
+    update_button.connect_clicked(move |button| {
+        tracing::info!("Update button clicked");
+        let _ = button.activate_action("win.show-page", Some(&Page::Update.name().to_variant()));
+    });
+    group.add(&update_row);
+
+    // --- Software Center ---
+    let software_row = libadwaita::ActionRow::new();
+    software_row.set_title("Software Center");
+    software_row.set_subtitle("Browse and install applications");
+    software_row.add_prefix(&gtk::Image::from_icon_name(
+        "system-software-install-symbolic",
+    ));
+    software_row.set_activatable(true);
+
+    let software_button = Button::with_label("Open");
+    software_button.set_valign(gtk::Align::Center);
+    software_row.add_suffix(&software_button);
+
+    let de_clone = de.clone();
+    software_button.connect_clicked(move |_| {
+        tracing::info!("Software Center button clicked");
+        let cmd = match de_clone.as_str() {
+            "KDE Plasma" => "discover",
+            "GNOME" => "gnome-software",
+            "Xfce" => "xfce4-appfinder",
+            "Cinnamon" | "MATE" | "Budgie" => "mintinstall",
+            _ => "gnome-software",
+        };
+
+        match std::process::Command::new(cmd).spawn() {
+            Ok(_) => crate::notifications::notify_success("Software Center opened"),
+            Err(e) => {
+                tracing::error!("Failed to open software center '{}': {}", cmd, e);
+                crate::notifications::notify_error("Failed to open software center");
+            }
+        }
+    });
+    group.add(&software_row);
+
+    // --- System Settings ---
+    let settings_row = libadwaita::ActionRow::new();
+    settings_row.set_title("System Settings");
+    settings_row.set_subtitle("Configure your system");
+    settings_row.add_prefix(&gtk::Image::from_icon_name("emblem-system-symbolic"));
+    settings_row.set_activatable(true);
+
+    let settings_button = Button::with_label("Open");
+    settings_button.set_valign(gtk::Align::Center);
+    settings_row.add_suffix(&settings_button);
+
+    let de_clone = de.clone();
+    settings_button.connect_clicked(move |_| {
+        tracing::info!("Settings button clicked");
+        let cmd = match de_clone.as_str() {
+            "KDE Plasma" => "systemsettings5",
+            "GNOME" => "gnome-control-center",
+            "Xfce" => "xfce4-settings-manager",
+            "Cinnamon" => "cinnamon-settings",
+            "MATE" => "mate-control-center",
+            _ => "gnome-control-center",
+        };
+
+        match std::process::Command::new(cmd).spawn() {
+            Ok(_) => crate::notifications::notify_success("System Settings opened"),
+            Err(e) => {
+                tracing::error!("Failed to open settings '{}': {}", cmd, e);
+                crate::notifications::notify_error("Failed to open system settings");
+            }
+        }
+    });
+    group.add(&settings_row);
+
+    group
+}
+
+/// Home page links: the distribution's own website, documentation and support
+/// pages from os-release, then the Ro-Start source code
+fn links(t: &crate::i18n::Translations, os_release: &OsRelease) -> Vec<(String, String)> {
+    [
+        (&t.home.website, os_release.home_url.as_deref()),
+        (&t.home.docs, os_release.documentation_url.as_deref()),
+        (&t.home.forum, os_release.support_url.as_deref()),
+        (&t.home.github, Some(PROJECT_URL)),
+    ]
+    .into_iter()
+    .filter_map(|(label, url)| Some((label.clone(), url?.to_string())))
+    .collect()
+}
+
+fn create_links_card(
+    t: &crate::i18n::Translations,
+    links: Vec<(String, String)>,
+) -> libadwaita::PreferencesGroup {
+    let group = libadwaita::PreferencesGroup::new();
+    group.set_title(&t.home.links_title);
+
+    for (label, url) in links {
+        let row = libadwaita::ActionRow::new();
+        row.set_title(&label);
+        row.set_subtitle(&glib::markup_escape_text(&url));
+        row.add_suffix(&gtk::Image::from_icon_name("adw-external-link-symbolic"));
+        row.set_activatable(true);
+        row.connect_activated(move |_| {
+            tracing::info!("Opening {}", url);
+            if let Err(e) =
+                gio::AppInfo::launch_default_for_uri(&url, None::<&gio::AppLaunchContext>)
+            {
+                tracing::error!("Failed to open {}: {}", url, e);
+                crate::notifications::notify_error("Failed to open link");
+            }
+        });
+        group.add(&row);
+    }
+
+    group
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_default_to_distro_urls() {
+        let t = crate::i18n::t();
+        let os_release = OsRelease::parse(
+            "HOME_URL=\"https://fedoraproject.org/\"\n\
+             SUPPORT_URL=\"https://ask.fedoraproject.org/\"\n",
+        );
+
+        let urls: Vec<String> = links(&t, &os_release)
+            .into_iter()
+            .map(|(_, url)| url)
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://fedoraproject.org/",
+                "https://ask.fedoraproject.org/",
+                PROJECT_URL
+            ]
+        );
+    }
+}
@@ -1,50 +1,30 @@
-use crate::system::SystemState;
+use crate::package_manager::{PackageManager, RestartStatus};
+use crate::ui::Page;
 use adw::prelude::*;
 use gio::prelude::*;
 use gtk::prelude::*;
-use gtk::{Application, ApplicationWindow, Button, HeaderBar, Label};
+use gtk::{Application, Label};
 use gtk::{Box as GtkBox, Orientation};
 use libadwaita as adw;
 
 #[allow(dead_code)]
 pub struct MainWindow {
-    window: ApplicationWindow,
+    window: adw::ApplicationWindow,
 }
 
 impl MainWindow {
     #[allow(clippy::new_ret_no_self)]
-    pub fn new(app: &Application) -> ApplicationWindow {
+    pub fn new(app: &Application) -> adw::ApplicationWindow {
         // Get translations
         let t = crate::i18n::t();
 
-        // Create main container
-        let main_box = GtkBox::new(Orientation::Vertical, 24);
-        main_box.set_margin_top(24);
-        main_box.set_margin_bottom(24);
-        main_box.set_margin_start(24);
-        main_box.set_margin_end(24);
-
-        // Welcome section
-        let welcome_box = GtkBox::new(Orientation::Vertical, 12);
-        let welcome_label = Label::new(Some(&t.home.title));
-        welcome_label.add_css_class("title-1");
-        let desc_label = Label::new(Some(&t.home.description));
-        desc_label.add_css_class("dim-label");
-        welcome_box.append(&welcome_label);
-        welcome_box.append(&desc_label);
-        main_box.append(&welcome_box);
-
-        // System info card
-        let sys_card = Self::create_system_info_card();
-        main_box.append(&sys_card);
-
-        // Quick actions card
-        let actions_card = Self::create_actions_card();
-        main_box.append(&actions_card);
-
-        // Create header bar with menu
-        let header = HeaderBar::new();
-        header.set_title_widget(Some(&Label::new(Some("Ro-Start"))));
+        // One stack child per sidebar entry
+        let stack = gtk::Stack::new();
+        stack.set_transition_type(gtk::StackTransitionType::Crossfade);
+        stack.set_vexpand(true);
+        for page in Page::ALL {
+            stack.add_named(&page.create(), Some(page.name()));
+        }
 
         // Create menu
         let menu = gio::Menu::new();
@@ -52,6 +32,9 @@ impl MainWindow {
         // Settings menu item
         menu.append(Some("_Settings"), Some("win.settings"));
 
+        // System report menu item
+        menu.append(Some("Generate System _Report"), Some("win.generate-report"));
+
         // About menu item
         menu.append(Some("_About"), Some("win.about"));
 
@@ -65,26 +48,81 @@ impl MainWindow {
         let menu_button = gtk::MenuButton::new();
         menu_button.set_icon_name("open-menu-symbolic");
         menu_button.set_menu_model(Some(&menu));
-        header.pack_end(&menu_button);
 
-        // Create scrolled window for content
-        let scrolled = gtk::ScrolledWindow::new();
-        scrolled.set_child(Some(&main_box));
-        scrolled.set_vexpand(true);
+        // Sidebar
+        let sidebar_list = gtk::ListBox::new();
+        sidebar_list.add_css_class("navigation-sidebar");
+        for page in Page::ALL {
+            let row_box = GtkBox::new(Orientation::Horizontal, 12);
+            row_box.set_margin_top(6);
+            row_box.set_margin_bottom(6);
+            row_box.append(&gtk::Image::from_icon_name(page.icon_name()));
+            row_box.append(&Label::new(Some(&page.title(&t))));
+            sidebar_list.append(&row_box);
+        }
+
+        let sidebar_header = adw::HeaderBar::new();
+        sidebar_header.pack_end(&menu_button);
+
+        let sidebar_toolbar = adw::ToolbarView::new();
+        sidebar_toolbar.add_top_bar(&sidebar_header);
+        sidebar_toolbar.set_content(Some(&sidebar_list));
+
+        let sidebar = adw::NavigationPage::new(&sidebar_toolbar, &t.sidebar.welcome);
+
+        // Restart banner stays above the content until the system is rebooted
+        let restart_banner = adw::Banner::new("");
+        restart_banner.set_button_label(Some("Restart now"));
+
+        let content_toolbar = adw::ToolbarView::new();
+        content_toolbar.add_top_bar(&adw::HeaderBar::new());
+        content_toolbar.add_top_bar(&restart_banner);
+        content_toolbar.set_content(Some(&stack));
+
+        let content = adw::NavigationPage::new(&content_toolbar, &Page::Home.title(&t));
+
+        let split_view = adw::NavigationSplitView::new();
+        split_view.set_sidebar(Some(&sidebar));
+        split_view.set_content(Some(&content));
 
         // Create main window
-        let window = ApplicationWindow::builder()
+        let window = adw::ApplicationWindow::builder()
             .application(app)
             .title("Ro-Start")
             .default_width(960)
             .default_height(640)
+            .width_request(360)
+            .height_request(294)
+            .content(&split_view)
             .build();
 
-        window.set_titlebar(Some(&header));
-        window.set_child(Some(&scrolled));
+        // Collapse the sidebar on narrow windows
+        let breakpoint = adw::Breakpoint::new(adw::BreakpointCondition::new_length(
+            adw::BreakpointConditionLengthType::MaxWidth,
+            600.0,
+            adw::LengthUnit::Sp,
+        ));
+        breakpoint.add_setter(&split_view, "collapsed", Some(&true.to_value()));
+        window.add_breakpoint(breakpoint);
+
+        sidebar_list.connect_row_selected({
+            let stack = stack.clone();
+            let split_view = split_view.clone();
+            move |_, row| {
+                let Some(page) = row.and_then(|row| Page::ALL.get(row.index() as usize)) else {
+                    return;
+                };
+                stack.set_visible_child_name(page.name());
+                content.set_title(&page.title(&crate::i18n::t()));
+                split_view.set_show_content(true);
+            }
+        });
+        sidebar_list.select_row(sidebar_list.row_at_index(0).as_ref());
 
         // Add actions
         Self::setup_actions(&window, app);
+        Self::setup_navigation(&window, &sidebar_list, &stack);
+        Self::setup_restart_status(&window, &restart_banner);
 
         // Load CSS for styling
         Self::load_css();
@@ -92,7 +130,45 @@ impl MainWindow {
         window
     }
 
-    fn setup_actions(window: &ApplicationWindow, app: &Application) {
+    /// Add `win.show-page(s)`, which selects a page in the sidebar, and
+    /// `win.start-upgrade`, which opens the update page and starts the upgrade
+    fn setup_navigation(
+        window: &adw::ApplicationWindow,
+        sidebar_list: &gtk::ListBox,
+        stack: &gtk::Stack,
+    ) {
+        let show_page = gio::SimpleAction::new("show-page", Some(glib::VariantTy::STRING));
+        let sidebar = sidebar_list.clone();
+        show_page.connect_activate(move |_, param| {
+            let Some(page) = param.and_then(|p| p.str()).and_then(Page::from_name) else {
+                tracing::warn!("Unknown page requested: {:?}", param);
+                return;
+            };
+            let index = Page::ALL.iter().position(|p| *p == page).unwrap_or(0);
+            sidebar.select_row(sidebar.row_at_index(index as i32).as_ref());
+        });
+        window.add_action(&show_page);
+
+        let start_upgrade = gio::SimpleAction::new("start-upgrade", None);
+        let window_weak = window.downgrade();
+        let stack = stack.clone();
+        start_upgrade.connect_activate(move |_, _| {
+            let Some(window) = window_weak.upgrade() else {
+                return;
+            };
+            ActionGroupExt::activate_action(
+                &window,
+                "show-page",
+                Some(&Page::Update.name().to_variant()),
+            );
+            if let Some(update_page) = stack.child_by_name(Page::Update.name()) {
+                let _ = update_page.activate_action("update.start", None);
+            }
+        });
+        window.add_action(&start_upgrade);
+    }
+
+    fn setup_actions(window: &adw::ApplicationWindow, app: &Application) {
         // Settings action
         let settings_action = gio::SimpleAction::new("settings", None);
         let window_weak = window.downgrade();
@@ -103,6 +179,16 @@ impl MainWindow {
         });
         window.add_action(&settings_action);
 
+        // System report action
+        let report_action = gio::SimpleAction::new("generate-report", None);
+        let window_weak = window.downgrade();
+        report_action.connect_activate(move |_, _| {
+            if let Some(window) = window_weak.upgrade() {
+                crate::ui::report::show_generate_report(window.upcast_ref::<gtk::Window>());
+            }
+        });
+        window.add_action(&report_action);
+
         // About action
         let about_action = gio::SimpleAction::new("about", None);
         let window_weak = window.downgrade();
@@ -129,165 +215,117 @@ impl MainWindow {
         app.set_accels_for_action("win.quit", &["<Ctrl>Q"]);
     }
 
-    fn create_system_info_card() -> libadwaita::PreferencesGroup {
-        let group = libadwaita::PreferencesGroup::new();
-
-        group.set_title("System Information");
-
-        // Get system info
-        let sys_state = SystemState::new();
-        let info = sys_state.get_system_info();
-
-        // OS Row
-        let os_row = libadwaita::ActionRow::new();
-        os_row.set_title("Operating System");
-        os_row.set_subtitle(&info.os_name);
-        os_row.add_prefix(&gtk::Image::from_icon_name("computer-symbolic"));
-        group.add(&os_row);
-
-        // Desktop Row
-        let desktop_row = libadwaita::ActionRow::new();
-        desktop_row.set_title("Desktop Environment");
-        desktop_row.set_subtitle(&info.desktop_environment);
-        desktop_row.add_prefix(&gtk::Image::from_icon_name("video-display-symbolic"));
-        group.add(&desktop_row);
-
-        // Kernel Row
-        let kernel_row = libadwaita::ActionRow::new();
-        kernel_row.set_title("Kernel");
-        kernel_row.set_subtitle(&info.kernel_version);
-        kernel_row.add_prefix(&gtk::Image::from_icon_name("utilities-terminal-symbolic"));
-        group.add(&kernel_row);
-
-        // Memory Row
-        let memory_row = libadwaita::ActionRow::new();
-        memory_row.set_title("Memory");
-        memory_row.set_subtitle(&info.memory_info);
-        memory_row.add_prefix(&gtk::Image::from_icon_name("drive-harddisk-symbolic"));
-        group.add(&memory_row);
-
-        // CPU Row
-        let cpu_row = libadwaita::ActionRow::new();
-        cpu_row.set_title("CPU");
-        cpu_row.set_subtitle(&info.cpu_info);
-        cpu_row.add_prefix(&gtk::Image::from_icon_name("cpu-symbolic"));
-        group.add(&cpu_row);
-
-        group
-    }
-
-    fn create_actions_card() -> libadwaita::PreferencesGroup {
-        let group = libadwaita::PreferencesGroup::new();
-        group.set_title("Quick Actions");
-
-        // Detect DE once and share across callbacks (avoids re-creating SystemState each click)
-        let sys_state = SystemState::new();
-        let de = std::rc::Rc::new(sys_state.get_system_info().desktop_environment);
+    /// Add `win.refresh-restart-status` and run it once at startup. The update
+    /// window activates it again after an upgrade.
+    fn setup_restart_status(window: &adw::ApplicationWindow, banner: &adw::Banner) {
+        // What the banner currently shows, for its button
+        let status = std::rc::Rc::new(std::cell::RefCell::new(RestartStatus::default()));
+
+        let refresh_action = gio::SimpleAction::new("refresh-restart-status", None);
+        refresh_action.connect_activate({
+            let banner = banner.clone();
+            let status = status.clone();
+            move |_, _| {
+                let handle = crate::runtime::spawn_blocking(|| {
+                    PackageManager::detect()
+                        .map(|pm| pm.restart_status())
+                        .unwrap_or_default()
+                });
+
+                let banner = banner.clone();
+                let status = status.clone();
+                glib::spawn_future_local(async move {
+                    let new_status = handle.await.unwrap_or_default();
+                    Self::show_restart_status(&banner, &new_status);
+                    *status.borrow_mut() = new_status;
+                });
+            }
+        });
+        window.add_action(&refresh_action);
 
-        // --- Update System ---
-        let update_row = libadwaita::ActionRow::new();
-        update_row.set_title("Update System");
-        update_row.set_subtitle("Check and install available updates");
-        update_row.add_prefix(&gtk::Image::from_icon_name(
-            "software-update-available-symbolic",
-        ));
-        update_row.set_activatable(true);
-
-        let update_button = Button::with_label("Update");
-        update_button.set_valign(gtk::Align::Center);
-        update_button.add_css_class("suggested-action");
-        update_row.add_suffix(&update_button);
-
-        let de_clone = de.clone();
-        update_button.connect_clicked(move |_| {
-            tracing::info!("Update button clicked");
-            let cmd = match de_clone.as_str() {
-                "KDE Plasma" => "discover",
-                "GNOME" => "gnome-software",
-                "Xfce" => "xfce4-appfinder",
-                "Cinnamon" | "MATE" | "Budgie" => "mintupdate",
-                _ => "gnome-software",
+        let window_weak = window.downgrade();
+        banner.connect_button_clicked(move |_| {
+            let Some(window) = window_weak.upgrade() else {
+                return;
             };
-
-            // Security: use Command::new directly — no shell interpretation
-            match std::process::Command::new(cmd).spawn() {
-                Ok(_) => crate::notifications::notify_success("Update Manager opened"),
-                Err(e) => {
-                    tracing::error!("Failed to open update manager '{}': {}", cmd, e);
-                    crate::notifications::notify_error("Failed to open update manager");
-                }
+            let status = status.borrow();
+            if status.reboot_required {
+                crate::ui::dialogs::show_confirm(
+                    Some(window.upcast_ref::<gtk::Window>()),
+                    "Restart now?",
+                    "Save your work before continuing. All open applications will be closed.",
+                    "Restart",
+                    Box::new(Self::reboot),
+                );
+            } else if !status.services.is_empty() {
+                // Outdated services only need restarting themselves, not the computer
+                let services = status.services.clone();
+                let window_weak = window.downgrade();
+                crate::ui::dialogs::show_confirm(
+                    Some(window.upcast_ref::<gtk::Window>()),
+                    "Restart services?",
+                    &format!("These services will be restarted: {}", services.join(", ")),
+                    "Restart Services",
+                    Box::new(move || Self::restart_services(&window_weak, services.clone())),
+                );
             }
         });
-        group.add(&update_row);
-
-        // --- Software Center ---
-        let software_row = libadwaita::ActionRow::new();
-        software_row.set_title("Software Center");
-        software_row.set_subtitle("Browse and install applications");
-        software_row.add_prefix(&gtk::Image::from_icon_name(
-            "system-software-install-symbolic",
-        ));
-        software_row.set_activatable(true);
-
-        let software_button = Button::with_label("Open");
-        software_button.set_valign(gtk::Align::Center);
-        software_row.add_suffix(&software_button);
-
-        let de_clone = de.clone();
-        software_button.connect_clicked(move |_| {
-            tracing::info!("Software Center button clicked");
-            let cmd = match de_clone.as_str() {
-                "KDE Plasma" => "discover",
-                "GNOME" => "gnome-software",
-                "Xfce" => "xfce4-appfinder",
-                "Cinnamon" | "MATE" | "Budgie" => "mintinstall",
-                _ => "gnome-software",
-            };
 
-            match std::process::Command::new(cmd).spawn() {
-                Ok(_) => crate::notifications::notify_success("Software Center opened"),
-                Err(e) => {
-                    tracing::error!("Failed to open software center '{}': {}", cmd, e);
-                    crate::notifications::notify_error("Failed to open software center");
-                }
-            }
+        refresh_action.activate(None);
+    }
+
+    fn show_restart_status(banner: &adw::Banner, status: &RestartStatus) {
+        if status.reboot_required {
+            tracing::info!("Reboot required by: {:?}", status.reboot_packages);
+            banner.set_title("Restart required to finish installing updates");
+            banner.set_button_label(Some("Restart now"));
+        } else if !status.services.is_empty() {
+            tracing::info!("Services using outdated libraries: {:?}", status.services);
+            banner.set_title(&format!(
+                "{} service(s) are running outdated libraries: {}",
+                status.services.len(),
+                status.services.join(", ")
+            ));
+            banner.set_button_label(Some("Restart Services"));
+        }
+        banner.set_revealed(status.needs_attention());
+    }
+
+    /// Restart the outdated services through pkexec, then check again
+    fn restart_services(window: &glib::WeakRef<adw::ApplicationWindow>, services: Vec<String>) {
+        let handle = crate::runtime::spawn_blocking(move || {
+            crate::services::restart_units(&services, crate::services::UnitScope::System)
         });
-        group.add(&software_row);
-
-        // --- System Settings ---
-        let settings_row = libadwaita::ActionRow::new();
-        settings_row.set_title("System Settings");
-        settings_row.set_subtitle("Configure your system");
-        settings_row.add_prefix(&gtk::Image::from_icon_name("emblem-system-symbolic"));
-        settings_row.set_activatable(true);
-
-        let settings_button = Button::with_label("Open");
-        settings_button.set_valign(gtk::Align::Center);
-        settings_row.add_suffix(&settings_button);
-
-        let de_clone = de.clone();
-        settings_button.connect_clicked(move |_| {
-            tracing::info!("Settings button clicked");
-            let cmd = match de_clone.as_str() {
-                "KDE Plasma" => "systemsettings5",
-                "GNOME" => "gnome-control-center",
-                "Xfce" => "xfce4-settings-manager",
-                "Cinnamon" => "cinnamon-settings",
-                "MATE" => "mate-control-center",
-                _ => "gnome-control-center",
-            };
 
-            match std::process::Command::new(cmd).spawn() {
-                Ok(_) => crate::notifications::notify_success("System Settings opened"),
-                Err(e) => {
-                    tracing::error!("Failed to open settings '{}': {}", cmd, e);
-                    crate::notifications::notify_error("Failed to open system settings");
+        let window = window.clone();
+        glib::spawn_future_local(async move {
+            match handle.await {
+                Ok(Ok(0)) => crate::notifications::notify_success("Services restarted"),
+                Ok(Ok(code)) => {
+                    tracing::error!("Restarting services exited with code {}", code);
+                    crate::notifications::notify_error("Failed to restart the services");
+                }
+                Ok(Err(e)) => {
+                    tracing::error!("Failed to restart services: {}", e);
+                    crate::notifications::notify_error("Failed to restart the services");
                 }
+                Err(e) => tracing::error!("Service restart task failed: {}", e),
+            }
+            if let Some(window) = window.upgrade() {
+                ActionGroupExt::activate_action(&window, "refresh-restart-status", None);
             }
         });
-        group.add(&settings_row);
+    }
 
-        group
+    fn reboot() {
+        tracing::info!("Rebooting the system");
+        if let Err(e) = std::process::Command::new("systemctl")
+            .arg("reboot")
+            .spawn()
+        {
+            tracing::error!("Failed to reboot: {}", e);
+            crate::notifications::notify_error("Failed to restart the system");
+        }
     }
 
     fn load_css() {
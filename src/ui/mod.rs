@@ -1,6 +1,167 @@
 pub mod about;
+pub mod boot;
+pub mod dashboard;
 pub mod dialogs;
+pub mod drivers;
+pub mod hardware;
+pub mod health;
+pub mod home;
 pub mod main_window;
+pub mod report;
+pub mod services;
 pub mod settings;
+pub mod software;
+pub mod update;
+pub mod whats_new;
 
 pub use main_window::MainWindow;
+
+use gtk::prelude::*;
+
+/// Pages of the main window sidebar, also used for deep links
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Page {
+    Home,
+    Dashboard,
+    Health,
+    Services,
+    Boot,
+    Update,
+    Drivers,
+    Hardware,
+    Software,
+}
+
+impl Page {
+    /// Sidebar order
+    pub const ALL: [Self; 9] = [
+        Self::Home,
+        Self::Dashboard,
+        Self::Health,
+        Self::Services,
+        Self::Boot,
+        Self::Update,
+        Self::Drivers,
+        Self::Hardware,
+        Self::Software,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Home => "home",
+            Self::Dashboard => "dashboard",
+            Self::Health => "health",
+            Self::Services => "services",
+            Self::Boot => "boot",
+            Self::Update => "update",
+            Self::Drivers => "drivers",
+            Self::Hardware => "hardware",
+            Self::Software => "software",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|page| page.name() == name)
+    }
+
+    /// Sidebar label
+    pub fn title(self, t: &crate::i18n::Translations) -> String {
+        match self {
+            Self::Home => t.sidebar.home.clone(),
+            Self::Dashboard => t.sidebar.dashboard.clone(),
+            Self::Health => t.sidebar.health.clone(),
+            Self::Services => t.sidebar.services.clone(),
+            Self::Boot => t.sidebar.boot.clone(),
+            Self::Update => t.sidebar.update.clone(),
+            Self::Drivers => t.sidebar.drivers.clone(),
+            Self::Hardware => t.sidebar.hardware.clone(),
+            Self::Software => t.sidebar.software.clone(),
+        }
+    }
+
+    pub fn icon_name(self) -> &'static str {
+        match self {
+            Self::Home => "go-home-symbolic",
+            Self::Dashboard => "utilities-system-monitor-symbolic",
+            Self::Health => "security-high-symbolic",
+            Self::Services => "system-run-symbolic",
+            Self::Boot => "system-reboot-symbolic",
+            Self::Update => "software-update-available-symbolic",
+            Self::Drivers => "video-display-symbolic",
+            Self::Hardware => "computer-symbolic",
+            Self::Software => "system-software-install-symbolic",
+        }
+    }
+
+    pub(crate) fn create(self) -> gtk::Widget {
+        match self {
+            Self::Home => home::create_page().upcast(),
+            Self::Dashboard => dashboard::create_page().upcast(),
+            Self::Health => health::create_page().upcast(),
+            Self::Services => services::create_page().upcast(),
+            Self::Boot => boot::create_page().upcast(),
+            Self::Update => update::create_page().upcast(),
+            Self::Drivers => drivers::create_page().upcast(),
+            Self::Hardware => hardware::create_page().upcast(),
+            Self::Software => software::create_page().upcast(),
+        }
+    }
+}
+
+/// Register the application-wide actions used for deep links. They can also be
+/// activated on an already running instance over D-Bus.
+///
+/// - `app.show-page(s)`: present the main window on the given page
+/// - `app.update-now`: open the update page and start installing updates
+pub fn setup_app_actions(app: &gtk::Application) {
+    let show_page = gio::SimpleAction::new("show-page", Some(glib::VariantTy::STRING));
+    let app_weak = app.downgrade();
+    show_page.connect_activate(move |_, param| {
+        let page = param.and_then(|p| p.str()).and_then(Page::from_name);
+        match (app_weak.upgrade(), page) {
+            (Some(app), Some(page)) => show_page_in(&app, page, false),
+            (_, None) => tracing::warn!("Unknown page requested: {:?}", param),
+            _ => {}
+        }
+    });
+    app.add_action(&show_page);
+
+    let update_now = gio::SimpleAction::new("update-now", None);
+    let app_weak = app.downgrade();
+    update_now.connect_activate(move |_, _| {
+        if let Some(app) = app_weak.upgrade() {
+            show_page_in(&app, Page::Update, true);
+        }
+    });
+    app.add_action(&update_now);
+}
+
+fn show_page_in(app: &gtk::Application, page: Page, start_upgrade: bool) {
+    let Some(window) = app
+        .windows()
+        .into_iter()
+        .find(|window| window.is::<gtk::ApplicationWindow>())
+    else {
+        tracing::warn!("No main window to show {:?} on", page);
+        return;
+    };
+    window.present();
+
+    let _ = window.activate_action("win.show-page", Some(&page.name().to_variant()));
+    if start_upgrade {
+        let _ = window.activate_action("win.start-upgrade", None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_names_roundtrip() {
+        for page in Page::ALL {
+            assert_eq!(Page::from_name(page.name()), Some(page));
+        }
+        assert_eq!(Page::from_name("welcome"), None);
+    }
+}
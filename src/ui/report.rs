@@ -0,0 +1,103 @@
+use crate::config::AppConfig;
+use crate::report::{Redaction, Report, ReportFormat};
+use adw::prelude::*;
+use gtk::prelude::*;
+use libadwaita as adw;
+use std::time::Duration;
+
+/// Ask whether to redact personal details, then save a report archive to the
+/// Downloads folder
+pub fn show_generate_report(parent: &gtk::Window) {
+    let dialog = adw::MessageDialog::builder()
+        .heading("Generate System Report")
+        .body(
+            "Collects system details, pending updates, graphics drivers, disk usage \
+             and recent errors into an archive you can attach to a forum post.",
+        )
+        .transient_for(parent)
+        .modal(true)
+        .build();
+
+    let redact_check = gtk::CheckButton::with_label("Hide hostname, user name and IP addresses");
+    redact_check.set_active(true);
+    dialog.set_extra_child(Some(&redact_check));
+
+    dialog.add_response("cancel", "Cancel");
+    dialog.add_response("generate", "Generate");
+    dialog.set_response_appearance("generate", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("generate"));
+    dialog.set_close_response("cancel");
+
+    let parent = parent.clone();
+    dialog.connect_response(Some("generate"), move |_, _| {
+        generate(&parent, redact_check.is_active());
+    });
+
+    dialog.present();
+}
+
+fn generate(parent: &gtk::Window, redact: bool) {
+    let config = AppConfig::load().unwrap_or_default();
+    let timeout = Duration::from_secs(config.update_check_timeout_secs);
+    let path = crate::report::default_archive_path();
+
+    let handle = crate::runtime::spawn({
+        let path = path.clone();
+        async move {
+            let mut report = Report::collect(timeout).await;
+            if redact {
+                report.redact(&Redaction::for_system(&report.system));
+            }
+            // Packing runs tar
+            tokio::task::spawn_blocking(move || report.write(ReportFormat::TarGz, Some(&path)))
+                .await
+                .unwrap_or_else(|e| Err(e.into()))
+        }
+    });
+
+    // The update check alone can take minutes
+    let progress = adw::MessageDialog::builder()
+        .heading("Generating System Report")
+        .body("Collecting system details and checking for updates. This can take a few minutes.")
+        .transient_for(parent)
+        .modal(true)
+        .build();
+    let spinner = gtk::Spinner::new();
+    spinner.start();
+    progress.set_extra_child(Some(&spinner));
+    progress.add_response("cancel", "Cancel");
+    progress.set_close_response("cancel");
+    let abort = handle.abort_handle();
+    progress.connect_response(None, move |_, _| abort.abort());
+    progress.present();
+
+    let parent = parent.clone();
+    glib::spawn_future_local(async move {
+        let result = handle.await;
+        progress.close();
+
+        match result {
+            Ok(Ok(())) => {
+                tracing::info!("System report saved to {}", path.display());
+                crate::ui::dialogs::show_info(
+                    Some(&parent),
+                    "System Report Saved",
+                    &format!(
+                        "The report was saved to {}. Attach it to your support request.",
+                        path.display()
+                    ),
+                );
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Failed to generate system report: {:#}", e);
+                crate::ui::dialogs::show_error(
+                    Some(&parent),
+                    "Failed to Generate Report",
+                    &format!("{:#}", e),
+                );
+            }
+            Err(e) if e.is_cancelled() => tracing::info!("System report cancelled"),
+            Err(e) => tracing::error!("System report task failed: {}", e),
+        }
+    });
+}
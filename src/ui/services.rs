@@ -0,0 +1,276 @@
+use crate::services::{FailedUnit, JournalEntry, UnitScope};
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+
+/// Journal errors listed on the page
+const RECENT_ERRORS: usize = 50;
+
+/// Build the Services page: failed system and user units with buttons to view
+/// their log or restart them, and the errors logged since boot
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let header = GtkBox::new(Orientation::Horizontal, 12);
+    let title_label = Label::new(Some(&t.sidebar.services));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    title_label.set_hexpand(true);
+    header.append(&title_label);
+
+    let refresh_button = Button::from_icon_name("view-refresh-symbolic");
+    refresh_button.set_tooltip_text(Some(&t.services.refresh));
+    refresh_button.set_valign(gtk::Align::Center);
+    header.append(&refresh_button);
+    content.append(&header);
+
+    // Holds the groups, replaced on every refresh
+    let results_box = GtkBox::new(Orientation::Vertical, 24);
+    content.append(&results_box);
+
+    refresh_button.connect_clicked({
+        let results_box = results_box.clone();
+        move |_| load(&results_box)
+    });
+
+    let loaded = std::cell::Cell::new(false);
+    content.connect_map(move |_| {
+        if !loaded.replace(true) {
+            load(&results_box);
+        }
+    });
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+struct Services {
+    failed: crate::error::Result<Vec<FailedUnit>>,
+    errors: crate::error::Result<Vec<JournalEntry>>,
+}
+
+fn load(results_box: &GtkBox) {
+    let group = adw::PreferencesGroup::new();
+    group.set_description(Some(&crate::i18n::t().services.loading));
+    clear(results_box);
+    results_box.append(&group);
+
+    let handle = crate::runtime::spawn_blocking(|| {
+        let failed = crate::services::failed_units(UnitScope::System).map(|mut units| {
+            units.extend(crate::services::failed_units(UnitScope::User).unwrap_or_default());
+            units
+        });
+        Services {
+            failed,
+            errors: crate::services::recent_errors(RECENT_ERRORS),
+        }
+    });
+
+    let results_box = results_box.clone();
+    glib::spawn_future_local(async move {
+        match handle.await {
+            Ok(services) => show(&results_box, services),
+            Err(e) => tracing::error!("Services task failed: {}", e),
+        }
+    });
+}
+
+fn clear(results_box: &GtkBox) {
+    while let Some(child) = results_box.first_child() {
+        results_box.remove(&child);
+    }
+}
+
+fn show(results_box: &GtkBox, services: Services) {
+    let t = crate::i18n::t();
+    clear(results_box);
+
+    let failed_group = adw::PreferencesGroup::new();
+    failed_group.set_title(&t.services.failed_title);
+    match services.failed {
+        Ok(units) if units.is_empty() => {
+            failed_group.set_description(Some(&t.services.no_failed));
+        }
+        Ok(units) => {
+            for unit in units {
+                failed_group.add(&failed_unit_row(results_box, unit));
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Could not list failed units: {}", e);
+            failed_group.set_description(Some(&t.services.failed_error));
+        }
+    }
+    results_box.append(&failed_group);
+
+    let errors_group = adw::PreferencesGroup::new();
+    errors_group.set_title(&t.services.errors_title);
+    match services.errors {
+        Ok(entries) if entries.is_empty() => {
+            errors_group.set_description(Some(&t.services.no_errors));
+        }
+        Ok(entries) => {
+            errors_group.set_description(Some(&t.services.errors_description));
+            for entry in entries {
+                errors_group.add(&journal_entry_row(&entry));
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Could not read the journal: {}", e);
+            errors_group.set_description(Some(&t.services.journal_error));
+        }
+    }
+    results_box.append(&errors_group);
+}
+
+fn failed_unit_row(results_box: &GtkBox, unit: FailedUnit) -> adw::ActionRow {
+    let t = crate::i18n::t();
+    let row = adw::ActionRow::new();
+    row.set_title(&glib::markup_escape_text(&unit.unit));
+    let mut subtitle = unit.description.clone();
+    if unit.scope == UnitScope::User {
+        subtitle.push_str(&format!(" ({})", t.services.user_unit));
+    }
+    row.set_subtitle(&glib::markup_escape_text(&subtitle));
+
+    let icon = gtk::Image::from_icon_name("dialog-error-symbolic");
+    icon.add_css_class("error");
+    row.add_prefix(&icon);
+
+    row.add_suffix(&view_log_button(&unit.unit, unit.scope));
+
+    let restart_button = Button::with_label(&t.services.restart);
+    restart_button.set_valign(gtk::Align::Center);
+    let results_box = results_box.clone();
+    restart_button.connect_clicked(move |button| {
+        button.set_sensitive(false);
+
+        let name = unit.unit.clone();
+        let scope = unit.scope;
+        let handle =
+            crate::runtime::spawn_blocking(move || crate::services::restart_units(&[name], scope));
+
+        let button = button.clone();
+        let results_box = results_box.clone();
+        let name = unit.unit.clone();
+        let t = crate::i18n::t();
+        glib::spawn_future_local(async move {
+            match handle.await {
+                Ok(Ok(0)) => {
+                    crate::notifications::notify_success(
+                        &t.services.restarted.replace("{unit}", &name),
+                    );
+                    load(&results_box);
+                }
+                Ok(Ok(code)) => {
+                    tracing::error!("Restarting {} exited with code {}", name, code);
+                    crate::notifications::notify_error(
+                        &t.services.restart_failed.replace("{unit}", &name),
+                    );
+                    button.set_sensitive(true);
+                }
+                Ok(Err(e)) => {
+                    tracing::error!("Restarting {} failed: {}", name, e);
+                    crate::notifications::notify_error(
+                        &t.services.restart_failed.replace("{unit}", &name),
+                    );
+                    button.set_sensitive(true);
+                }
+                Err(e) => tracing::error!("Restart task failed: {}", e),
+            }
+        });
+    });
+    row.add_suffix(&restart_button);
+
+    row
+}
+
+fn journal_entry_row(entry: &JournalEntry) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    let source = entry
+        .identifier
+        .as_deref()
+        .or(entry.unit.as_deref())
+        .unwrap_or("kernel");
+    let time = glib::DateTime::from_unix_local((entry.timestamp_usec / 1_000_000) as i64)
+        .and_then(|time| time.format("%H:%M:%S"))
+        .map(|time| time.to_string())
+        .unwrap_or_default();
+    row.set_title(&glib::markup_escape_text(&format!("{} {}", time, source)));
+    row.set_subtitle(&glib::markup_escape_text(&entry.message));
+    row.set_subtitle_lines(3);
+    row.set_subtitle_selectable(true);
+
+    if let Some(unit) = &entry.unit {
+        row.add_suffix(&view_log_button(unit, entry.scope));
+    }
+    row
+}
+
+fn view_log_button(unit: &str, scope: UnitScope) -> Button {
+    let button = Button::from_icon_name("text-x-generic-symbolic");
+    button.set_tooltip_text(Some(&crate::i18n::t().services.view_log));
+    button.set_valign(gtk::Align::Center);
+    button.add_css_class("flat");
+
+    let unit = unit.to_string();
+    button.connect_clicked(move |button| {
+        let parent = button.root().and_downcast::<gtk::Window>();
+        show_log(parent.as_ref(), &unit, scope);
+    });
+    button
+}
+
+/// Open a window with the unit's log since boot
+fn show_log(parent: Option<&gtk::Window>, unit: &str, scope: UnitScope) {
+    let t = crate::i18n::t();
+    let text_view = gtk::TextView::new();
+    text_view.set_editable(false);
+    text_view.set_monospace(true);
+    text_view.set_wrap_mode(gtk::WrapMode::WordChar);
+    text_view.set_top_margin(12);
+    text_view.set_bottom_margin(12);
+    text_view.set_left_margin(12);
+    text_view.set_right_margin(12);
+    text_view.buffer().set_text(&t.services.loading);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&text_view));
+    scrolled.set_vexpand(true);
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+    toolbar.set_content(Some(&scrolled));
+
+    let window = adw::Window::builder()
+        .title(unit)
+        .default_width(800)
+        .default_height(500)
+        .content(&toolbar)
+        .build();
+    if let Some(parent) = parent {
+        window.set_transient_for(Some(parent));
+    }
+    window.present();
+
+    let name = unit.to_string();
+    let handle = crate::runtime::spawn_blocking(move || crate::services::unit_log(&name, scope));
+
+    glib::spawn_future_local(async move {
+        let text = match handle.await {
+            Ok(Ok(log)) if log.trim().is_empty() => t.services.no_log.clone(),
+            Ok(Ok(log)) => log,
+            Ok(Err(e)) => t.services.log_error.replace("{error}", &e.to_string()),
+            Err(e) => t.services.log_error.replace("{error}", &e.to_string()),
+        };
+        text_view.buffer().set_text(&text);
+    });
+}
@@ -84,6 +84,12 @@ pub fn show_settings(parent: Option<&gtk::Window>) {
                 tracing::error!("Failed to save config: {}", e);
             }
         }
+
+        // Create or hide the user's XDG autostart entry
+        if let Err(e) = crate::autostart::set_enabled(active) {
+            tracing::error!("Failed to update autostart entry: {}", e);
+            crate::notifications::notify_error("Failed to change the login startup setting");
+        }
     });
 
     startup_group.add(&autostart_row);
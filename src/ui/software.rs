@@ -0,0 +1,73 @@
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use libadwaita as adw;
+
+/// Recommended applications: category id, name and AppStream id
+const RECOMMENDED: &[(&str, &str, &str)] = &[
+    ("web", "Firefox", "org.mozilla.firefox"),
+    ("editor", "Visual Studio Code", "com.visualstudio.code"),
+    ("music", "Spotify", "com.spotify.Client"),
+    ("chat", "Discord", "com.discordapp.Discord"),
+    ("graphics", "GIMP", "org.gimp.GIMP"),
+    ("media", "VLC", "org.videolan.VLC"),
+];
+
+/// Build the Software page with recommended applications
+pub fn create_page() -> gtk::ScrolledWindow {
+    let t = crate::i18n::t();
+
+    let content = GtkBox::new(Orientation::Vertical, 24);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let header = GtkBox::new(Orientation::Vertical, 12);
+    let title_label = Label::new(Some(&t.software.title));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    let desc_label = Label::new(Some(&t.software.description));
+    desc_label.add_css_class("dim-label");
+    desc_label.set_wrap(true);
+    desc_label.set_xalign(0.0);
+    header.append(&title_label);
+    header.append(&desc_label);
+    content.append(&header);
+
+    let group = adw::PreferencesGroup::new();
+    for &(category, name, app_id) in RECOMMENDED {
+        let row = adw::ActionRow::new();
+        row.set_title(name);
+        row.set_subtitle(
+            t.software
+                .categories
+                .get(category)
+                .map(String::as_str)
+                .unwrap_or(category),
+        );
+
+        let install_button = Button::with_label(&t.software.btn_install);
+        install_button.set_valign(gtk::Align::Center);
+        install_button.connect_clicked(move |_| open_in_software_center(app_id));
+        row.add_suffix(&install_button);
+
+        group.add(&row);
+    }
+    content.append(&group);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&content));
+    scrolled.set_vexpand(true);
+    scrolled
+}
+
+/// Open the application's page in the software center
+fn open_in_software_center(app_id: &str) {
+    tracing::info!("Opening {} in the software center", app_id);
+    let uri = format!("appstream://{}", app_id);
+    if let Err(e) = gio::AppInfo::launch_default_for_uri(&uri, None::<&gio::AppLaunchContext>) {
+        tracing::error!("Failed to open {}: {}", uri, e);
+        crate::notifications::notify_error("Failed to open software center");
+    }
+}
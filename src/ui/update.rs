@@ -0,0 +1,327 @@
+use crate::error::RoStartError;
+use crate::package_manager::{
+    CancelToken, PackageManager, UpdateCategory, UpdateCheckOptions, UpdateCheckProgress,
+    UpdateInfo,
+};
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Button, Label, Orientation};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Messages sent from the upgrade thread to the GTK main loop
+enum UpgradeEvent {
+    Line(String),
+    Finished(crate::error::Result<i32>),
+}
+
+/// Widgets and state the update check updates when it finishes
+#[derive(Clone)]
+struct UpdatePage {
+    backends: Rc<RefCell<Vec<PackageManager>>>,
+    start_requested: Rc<Cell<bool>>,
+    /// The update check finished, so upgrading cannot contend with it
+    check_finished: Rc<Cell<bool>>,
+    spinner: gtk::Spinner,
+    status_label: Label,
+    log_view: gtk::TextView,
+    start_button: Button,
+}
+
+/// Build the System Update page: pending updates and a live log of the
+/// upgrade. The check runs when the page is first shown. The page installs an
+/// `update.start` action that starts the upgrade.
+pub fn create_page() -> GtkBox {
+    let t = crate::i18n::t();
+
+    let content = GtkBox::new(Orientation::Vertical, 12);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let title_label = Label::new(Some(&t.update.title));
+    title_label.add_css_class("title-1");
+    title_label.set_xalign(0.0);
+    content.append(&title_label);
+
+    let desc_label = Label::new(Some(&t.update.description));
+    desc_label.add_css_class("dim-label");
+    desc_label.set_wrap(true);
+    desc_label.set_xalign(0.0);
+    content.append(&desc_label);
+
+    // Status line and start button
+    let status_box = GtkBox::new(Orientation::Horizontal, 12);
+    let spinner = gtk::Spinner::new();
+    status_box.append(&spinner);
+    let status_label = Label::new(Some(&t.update.status_unknown));
+    status_label.set_hexpand(true);
+    status_label.set_xalign(0.0);
+    status_label.set_wrap(true);
+    status_box.append(&status_label);
+
+    let start_button = Button::with_label(&t.update.btn_update);
+    start_button.add_css_class("suggested-action");
+    start_button.set_valign(gtk::Align::Center);
+    status_box.append(&start_button);
+    content.append(&status_box);
+
+    // Log pane
+    let log_label = Label::new(Some(&t.update.log_title));
+    log_label.add_css_class("heading");
+    log_label.set_xalign(0.0);
+    content.append(&log_label);
+
+    let log_view = gtk::TextView::new();
+    log_view.set_editable(false);
+    log_view.set_cursor_visible(false);
+    log_view.set_monospace(true);
+    log_view.set_wrap_mode(gtk::WrapMode::WordChar);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&log_view));
+    scrolled.set_vexpand(true);
+    scrolled.add_css_class("card");
+    content.append(&scrolled);
+
+    // Native package manager first, then Flatpak/Snap when installed. Probing
+    // them runs commands, so it happens with the first check and upgrading is
+    // possible once they are known.
+    let backends: Rc<RefCell<Vec<PackageManager>>> = Rc::default();
+    // "Update now" requested before the update check finished
+    let start_requested = Rc::new(Cell::new(false));
+    let check_finished = Rc::new(Cell::new(false));
+    start_button.set_sensitive(false);
+
+    // Check for updates in the background once the page is first shown
+    let cancel = CancelToken::new();
+    content.connect_unrealize({
+        let cancel = cancel.clone();
+        move |_| cancel.cancel()
+    });
+    let checked = Cell::new(false);
+    content.connect_map({
+        let page = UpdatePage {
+            backends: backends.clone(),
+            start_requested: start_requested.clone(),
+            check_finished: check_finished.clone(),
+            spinner: spinner.clone(),
+            status_label: status_label.clone(),
+            log_view: log_view.clone(),
+            start_button: start_button.clone(),
+        };
+        move |_| {
+            if !checked.replace(true) {
+                start_update_check(&page, cancel.clone());
+            }
+        }
+    });
+
+    let status_label_clone = status_label.clone();
+    let log_view_clone = log_view.clone();
+    start_button.connect_clicked(move |button| {
+        let backends = backends.borrow().clone();
+        let t = crate::i18n::t();
+
+        button.set_sensitive(false);
+        status_label_clone.set_text(&t.update.status_started);
+        log_view_clone.buffer().set_text("");
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        std::thread::spawn(move || {
+            let result = run_upgrades(&backends, |line| {
+                let _ = tx.send(UpgradeEvent::Line(line));
+            });
+            let _ = tx.send(UpgradeEvent::Finished(result));
+        });
+
+        let button = button.clone();
+        let status_label = status_label_clone.clone();
+        let log_view = log_view_clone.clone();
+        glib::spawn_future_local(async move {
+            while let Some(event) = rx.recv().await {
+                match event {
+                    UpgradeEvent::Line(line) => append_log(&log_view, &line),
+                    UpgradeEvent::Finished(result) => {
+                        let message = match result {
+                            Ok(0) => {
+                                crate::notifications::notify_success(&t.update.success);
+                                t.update.success.clone()
+                            }
+                            Ok(code) => {
+                                crate::notifications::notify_error(&t.update.error);
+                                format!("{} (exit code {})", t.update.error, code)
+                            }
+                            Err(e) => {
+                                crate::notifications::notify_error(&t.update.error);
+                                format!("{} ({})", t.update.error, e)
+                            }
+                        };
+                        status_label.set_text(&message);
+                        button.set_sensitive(true);
+
+                        // Let the main window check whether a reboot is now pending
+                        let _ = button.activate_action("win.refresh-restart-status", None);
+                    }
+                }
+            }
+        });
+    });
+
+    let actions = gio::SimpleActionGroup::new();
+    let start_action = gio::SimpleAction::new("start", None);
+    start_action.connect_activate(move |_, _| {
+        if start_button.is_sensitive() {
+            start_button.emit_clicked();
+        } else if !check_finished.get() {
+            start_requested.set(true);
+        }
+    });
+    actions.add_action(&start_action);
+    content.insert_action_group("update", Some(&actions));
+
+    content
+}
+
+/// Upgrade every backend in turn, stopping at the first one that fails.
+/// Returns the exit code of the last upgrade that ran.
+fn run_upgrades(
+    backends: &[PackageManager],
+    mut on_line: impl FnMut(String),
+) -> crate::error::Result<i32> {
+    let mut code = 0;
+    for pm in backends {
+        on_line(format!("==> {}", pm));
+        code = pm.run_upgrade(&mut on_line)?;
+        if code != 0 {
+            break;
+        }
+    }
+    Ok(code)
+}
+
+/// Append a line to the log and keep the view scrolled to the bottom
+fn append_log(view: &gtk::TextView, line: &str) {
+    let buffer = view.buffer();
+    let mut end = buffer.end_iter();
+    buffer.insert(&mut end, line);
+    buffer.insert(&mut end, "\n");
+
+    let mark = buffer.create_mark(None, &buffer.end_iter(), false);
+    view.scroll_mark_onscreen(&mark);
+    buffer.delete_mark(&mark);
+}
+
+/// Detect the update backends off the main thread and check them for updates
+fn start_update_check(page: &UpdatePage, cancel: CancelToken) {
+    page.spinner.start();
+    let handle = crate::runtime::spawn_blocking(PackageManager::detect_all);
+
+    let page = page.clone();
+    glib::spawn_future_local(async move {
+        let backends = handle.await.unwrap_or_default();
+
+        if backends.is_empty() {
+            page.check_finished.set(true);
+            let e = RoStartError::PackageManagerNotFound;
+            tracing::error!("Cannot check for updates: {}", e);
+            page.spinner.stop();
+            page.status_label
+                .set_text(&format!("{} ({})", crate::i18n::t().update.error, e));
+            return;
+        }
+
+        *page.backends.borrow_mut() = backends.clone();
+        check_updates(&page, backends, cancel);
+    });
+}
+
+/// Run the asynchronous update check and show its result in the window. Upgrading
+/// is enabled only once the check is over, so the two never hold the package
+/// manager lock at the same time.
+fn check_updates(page: &UpdatePage, backends: Vec<PackageManager>, cancel: CancelToken) {
+    let timeout_secs = crate::config::AppConfig::load()
+        .map(|config| config.update_check_timeout_secs)
+        .unwrap_or_else(|_| crate::package_manager::DEFAULT_UPDATE_CHECK_TIMEOUT.as_secs());
+    let options = UpdateCheckOptions {
+        timeout: std::time::Duration::from_secs(timeout_secs),
+        cancel,
+    };
+
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let handle = crate::runtime::spawn(async move {
+        PackageManager::check_all_updates_async(&backends, options, move |progress| {
+            let _ = progress_tx.send(progress);
+        })
+        .await
+    });
+
+    let spinner = page.spinner.clone();
+    glib::spawn_future_local(async move {
+        while let Some(progress) = progress_rx.recv().await {
+            tracing::debug!("Update check progress: {:?}", progress);
+            spinner.set_spinning(progress != UpdateCheckProgress::Done);
+        }
+        spinner.stop();
+    });
+
+    let page = page.clone();
+    glib::spawn_future_local(async move {
+        let t = crate::i18n::t();
+        let result = handle
+            .await
+            .unwrap_or_else(|e| Err(RoStartError::UpdateCheckFailed(e.to_string())));
+
+        match result {
+            Ok(info) => show_update_info(&info, &page.status_label, &page.log_view),
+            Err(RoStartError::UpdateCheckCancelled) => return,
+            Err(e) => {
+                tracing::error!("Update check failed: {}", e);
+                page.status_label
+                    .set_text(&format!("{} ({})", t.update.error, e));
+            }
+        }
+
+        page.check_finished.set(true);
+        page.start_button.set_sensitive(true);
+        if page.start_requested.replace(false) {
+            page.start_button.emit_clicked();
+        }
+    });
+}
+
+/// Show the pending updates that the upgrade is going to install
+fn show_update_info(info: &UpdateInfo, status_label: &Label, log_view: &gtk::TextView) {
+    let t = crate::i18n::t();
+
+    if !info.available {
+        status_label.set_text(&t.update.status_uptodate);
+        return;
+    }
+
+    if info.security_count > 0 {
+        status_label.set_text(&format!(
+            "{} ({}, {} security)",
+            t.update.status_need_update, info.count, info.security_count
+        ));
+    } else {
+        status_label.set_text(&format!("{} ({})", t.update.status_need_update, info.count));
+    }
+    for source in info.sources.iter().filter(|s| !s.updates.is_empty()) {
+        append_log(
+            log_view,
+            &format!("==> {} ({})", source.package_manager, source.updates.len()),
+        );
+        for update in &source.updates {
+            match (&update.category, update.severity) {
+                (UpdateCategory::Other, _) => append_log(log_view, &update.to_string()),
+                (category, Some(severity)) => append_log(
+                    log_view,
+                    &format!("{} [{}, {}]", update, category, severity),
+                ),
+                (category, None) => append_log(log_view, &format!("{} [{}]", update, category)),
+            }
+        }
+    }
+}
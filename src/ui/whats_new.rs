@@ -0,0 +1,136 @@
+use crate::config::{AppConfig, WelcomeReason};
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Box as GtkBox, Label, Orientation};
+use libadwaita as adw;
+
+const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+
+/// Open the "What's new" window when Ro-Start or the distribution changed
+/// since the welcome window was last seen, then remember this release
+pub fn show_if_needed(parent: &gtk::Window) {
+    let mut config = AppConfig::load().unwrap_or_default();
+    let app_version = env!("CARGO_PKG_VERSION");
+    let distro = crate::system::distro_release();
+
+    let Some(reason) = config.welcome_reason(app_version, &distro) else {
+        return;
+    };
+    tracing::info!("Showing what's new: {:?}", reason);
+
+    // Nothing to announce on first run; the welcome window is enough
+    if reason != WelcomeReason::FirstRun {
+        show_whats_new(Some(parent), &reason, &distro);
+    }
+
+    config.mark_welcome_seen(app_version, &distro, crate::config::unix_now());
+    if let Err(e) = config.save() {
+        tracing::error!("Failed to save config: {}", e);
+    }
+}
+
+/// Show the release notes of this version and what changed on the system
+pub fn show_whats_new(parent: Option<&gtk::Window>, reason: &WelcomeReason, distro: &str) {
+    let app_version = env!("CARGO_PKG_VERSION");
+
+    let window = adw::Window::new();
+    window.set_title(Some("What's New"));
+    window.set_default_size(560, 480);
+
+    if let Some(parent) = parent {
+        window.set_transient_for(Some(parent));
+        window.set_modal(true);
+    }
+
+    let content = GtkBox::new(Orientation::Vertical, 12);
+    content.set_margin_top(12);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let summary = match reason {
+        WelcomeReason::FirstRun => format!("Welcome to Ro-Start {}", app_version),
+        WelcomeReason::AppUpdated { from: Some(from) } => {
+            format!("Ro-Start was updated from {} to {}", from, app_version)
+        }
+        WelcomeReason::AppUpdated { from: None } => {
+            format!("Ro-Start was updated to {}", app_version)
+        }
+        WelcomeReason::DistroUpgraded { from: Some(from) } => {
+            format!("Your system was upgraded from {} to {}", from, distro)
+        }
+        WelcomeReason::DistroUpgraded { from: None } => {
+            format!("Your system was upgraded to {}", distro)
+        }
+    };
+    let summary_label = Label::new(Some(&summary));
+    summary_label.add_css_class("title-2");
+    summary_label.set_wrap(true);
+    summary_label.set_xalign(0.0);
+    content.append(&summary_label);
+
+    let notes_group = adw::PreferencesGroup::new();
+    notes_group.set_title(&format!("Ro-Start {}", app_version));
+    let notes = release_notes(CHANGELOG, app_version);
+    if notes.is_empty() {
+        notes_group.set_description(Some("No release notes for this version."));
+    }
+    for note in notes {
+        let row = adw::ActionRow::new();
+        row.set_title(&glib::markup_escape_text(&note));
+        row.set_title_lines(0);
+        notes_group.add(&row);
+    }
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_child(Some(&notes_group));
+    scrolled.set_vexpand(true);
+    scrolled.set_hscrollbar_policy(gtk::PolicyType::Never);
+    content.append(&scrolled);
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+    toolbar.set_content(Some(&content));
+    window.set_content(Some(&toolbar));
+
+    window.present();
+}
+
+/// Bullet points of the `## [version]` section of a Keep a Changelog file
+fn release_notes(changelog: &str, version: &str) -> Vec<String> {
+    let heading = format!("## [{}]", version);
+
+    changelog
+        .lines()
+        .skip_while(|line| !line.starts_with(&heading))
+        .skip(1)
+        .take_while(|line| !line.starts_with("## "))
+        .filter_map(|line| line.strip_prefix("- "))
+        .map(|note| note.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_notes_for_version() {
+        let changelog = "# Changelog\n\n\
+                         ## [Unreleased]\n\n- Upcoming\n\n\
+                         ## [2.0.0] - 2026-02-01\n\n### Added\n\n- Deb packages\n- Man page\n\n\
+                         ### Fixed\n\n- Icon reference\n\n\
+                         ## [1.0.0] - 2026-01-31\n\n- Rewrite\n";
+
+        assert_eq!(
+            release_notes(changelog, "2.0.0"),
+            ["Deb packages", "Man page", "Icon reference"]
+        );
+        assert!(release_notes(changelog, "3.0.0").is_empty());
+    }
+
+    #[test]
+    fn test_current_version_has_release_notes() {
+        assert!(!release_notes(CHANGELOG, env!("CARGO_PKG_VERSION")).is_empty());
+    }
+}
@@ -22,6 +22,7 @@ echo "  ✅ Binary removed"
 # Remove desktop files
 rm -f /usr/share/applications/ro-start.desktop
 rm -f /etc/xdg/autostart/ro-start-autostart.desktop
+rm -f /usr/lib/systemd/user/ro-start-update-check.service
 echo "  ✅ Desktop files removed"
 
 # Remove icon